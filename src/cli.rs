@@ -0,0 +1,260 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use clap::{Args, Parser, Subcommand};
+use serde_json::Value;
+
+use crate::{
+    client::Client,
+    config::{list_serial_ports, Config, SerialPortSelection, SerialPortSelector},
+    epson_codec::{Power, Source},
+    epson_serial_port::EpsonSerialPort,
+    routes::{
+        get_serial_ports::{GetSerialPortsResponse, SerialPortResponse},
+        get_status::GetStatusResponse,
+        post_power::PostPowerRequest,
+        post_source::PostSourceRequest,
+    },
+    state::EpsonState,
+    util::from_serde_name,
+};
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Network bridge for RS-232 Epson projectors")]
+pub struct Cli {
+    #[command(flatten)]
+    pub config: ConfigArgs,
+
+    /// Send commands to the bridge running at this URL instead of the local
+    /// serial port, e.g. http://epson.local
+    #[arg(long, global = true)]
+    pub url: Option<String>,
+
+    /// Print results as JSON.
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Defaults to `serve`.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Override the config file and environment variables.
+#[derive(Args, Debug)]
+pub struct ConfigArgs {
+    /// YAML config file, also read from CONFIG_FILE.
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Searched for when neither this nor SERIAL_PORT is set.
+    #[arg(long, global = true)]
+    pub serial_port: Option<String>,
+
+    /// Seconds to wait for a reply from the projector.
+    #[arg(long, global = true)]
+    pub timeout: Option<u64>,
+
+    /// Only used by `serve`.
+    #[arg(long, global = true)]
+    pub http_port: Option<u16>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run the bridge.
+    Serve,
+    /// Turn the projector on or off.
+    Power {
+        /// on or off
+        #[arg(value_parser = parse_serde_name::<Power>)]
+        power: Power,
+    },
+    /// Switch the projector source.
+    Source {
+        /// input1, input2DSub15, input2Rgb, input3Hdmi, input3DigitalRgb, video,
+        /// videoRca or hdmi2
+        #[arg(value_parser = parse_serde_name::<Source>)]
+        source: Source,
+    },
+    /// Print the projector status.
+    Status,
+    /// Print the serial ports found on this machine, or the bridge at
+    /// `--url`, and which one is used.
+    ListPorts,
+}
+
+fn parse_serde_name<T: serde::de::DeserializeOwned>(name: &str) -> Result<T, String> {
+    from_serde_name(name).ok_or_else(|| format!("unknown name {name}"))
+}
+
+/// Runs a one-shot command against the serial port, or the bridge at `--url`,
+/// without starting any of the servers.
+pub async fn run_command(cli: &Cli, command: Command) -> Result<()> {
+    if let Command::ListPorts = command {
+        let ports = match &cli.url {
+            Some(url) => Client::new(url).get_serial_ports().await?,
+            None => list_local_ports(&cli.config)?,
+        };
+        if cli.json {
+            println!("{}", serde_json::to_string_pretty(&ports)?);
+        } else {
+            print_ports(&ports);
+        }
+        return Ok(());
+    }
+
+    let output = match &cli.url {
+        Some(url) => run_remote(&Client::new(url), command).await?,
+        None => run_local(&cli.config, command).await?,
+    };
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print_pretty(&output);
+    }
+    Ok(())
+}
+
+async fn run_local(args: &ConfigArgs, command: Command) -> Result<Value> {
+    let config = Config::new(args, "warn")?;
+    let epson = EpsonSerialPort::new(&config).await?;
+    let output = match command {
+        Command::Power { power } => {
+            epson.set_power(power).await?;
+            serde_json::to_value(PostPowerRequest { power })?
+        }
+        Command::Source { source } => {
+            epson.set_source(source).await?;
+            serde_json::to_value(PostSourceRequest { source })?
+        }
+        Command::Status => {
+            let state = EpsonState::new(epson);
+            let status = GetStatusResponse::from(state.refresh_status(true).await?);
+            serde_json::to_value(status)?
+        }
+        Command::Serve | Command::ListPorts => return Err(anyhow!("not a one-shot command")),
+    };
+    Ok(output)
+}
+
+async fn run_remote(client: &Client, command: Command) -> Result<Value> {
+    let output = match command {
+        Command::Power { power } => {
+            let req = PostPowerRequest { power };
+            client.post_power(&req).await?;
+            serde_json::to_value(req)?
+        }
+        Command::Source { source } => {
+            let req = PostSourceRequest { source };
+            client.post_source(&req).await?;
+            serde_json::to_value(req)?
+        }
+        Command::Status => serde_json::to_value(client.get_status().await?)?,
+        Command::Serve | Command::ListPorts => return Err(anyhow!("not a one-shot command")),
+    };
+    Ok(output)
+}
+
+fn list_local_ports(args: &ConfigArgs) -> Result<GetSerialPortsResponse> {
+    let selector = SerialPortSelector::from_args(args)?;
+    let ports: Vec<SerialPortResponse> = list_serial_ports(&selector)?
+        .into_iter()
+        .map(SerialPortResponse::from)
+        .collect();
+    Ok(GetSerialPortsResponse {
+        in_use: None,
+        ports,
+    })
+}
+
+/// One line per port, e.g.
+/// `* /dev/ttyUSB0 usb 067b:2303 serial=A1 manufacturer=Prolific (only candidate)`
+fn print_ports(resp: &GetSerialPortsResponse) {
+    if resp.ports.is_empty() {
+        println!("no serial ports found");
+    }
+    for port in &resp.ports {
+        let marker = if port.selected { '*' } else { ' ' };
+        let mut line = format!("{marker} {} {}", port.name, port.port_type);
+        if let (Some(vid), Some(pid)) = (port.vid, port.pid) {
+            line.push_str(&format!(" {vid:04x}:{pid:04x}"));
+        }
+        if let Some(serial_number) = &port.serial_number {
+            line.push_str(&format!(" serial={serial_number}"));
+        }
+        if let Some(manufacturer) = &port.manufacturer {
+            line.push_str(&format!(" manufacturer={manufacturer}"));
+        }
+        line.push_str(&format!(" ({})", selection_reason(port.selection)));
+        println!("{line}");
+    }
+    if let Some(in_use) = &resp.in_use {
+        println!("in use: {in_use}");
+    }
+}
+
+fn selection_reason(selection: SerialPortSelection) -> &'static str {
+    match selection {
+        SerialPortSelection::Configured => "configured",
+        SerialPortSelection::NotConfigured => "another port is configured",
+        SerialPortSelection::OnlyCandidate => "only candidate",
+        SerialPortSelection::Ambiguous => "ambiguous, set SERIAL_PORT or a USB filter",
+        SerialPortSelection::Excluded => "excluded",
+        SerialPortSelection::NotMatched => "does not match the USB filter",
+    }
+}
+
+/// Order of the fields printed by `print_pretty`, any others follow sorted by
+/// name.
+const FIELD_ORDER: [&str; 8] = [
+    "power",
+    "powerStatus",
+    "source",
+    "mute",
+    "aspect",
+    "lampHours",
+    "error",
+    "lastUpdated",
+];
+
+/// Prints one `key: value` line per field, skipping empty ones.
+fn print_pretty(output: &Value) {
+    let Value::Object(fields) = output else {
+        return;
+    };
+    let mut fields: Vec<_> = fields.iter().collect();
+    fields.sort_by_key(|(key, _)| {
+        let position = FIELD_ORDER.iter().position(|field| field == key);
+        (position.unwrap_or(FIELD_ORDER.len()), key.as_str())
+    });
+    for (key, value) in fields {
+        match value {
+            Value::Null => {}
+            Value::String(value) => println!("{key}: {value}"),
+            value => println!("{key}: {value}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse_args() {
+        let cli = Cli::parse_from(["bridge", "source", "input3Hdmi", "--timeout", "5"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Source {
+                source: Source::Input3Hdmi
+            })
+        ));
+        assert_eq!(Some(5), cli.config.timeout);
+
+        let cli = Cli::parse_from(["bridge", "--serial-port", "/dev/ttyUSB1"]);
+        assert!(cli.command.is_none());
+        assert_eq!(Some("/dev/ttyUSB1".to_string()), cli.config.serial_port);
+
+        assert!(Cli::try_parse_from(["bridge", "power", "standby"]).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::{anyhow, Context, Result};
+use reqwest::StatusCode;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::routes::{
+    get_serial_ports::GetSerialPortsResponse, get_status::GetStatusResponse,
+    post_power::PostPowerRequest, post_source::PostSourceRequest, EmptyResponse, ErrorResponse,
+};
+
+/// Talks to another running bridge through its HTTP API.
+pub struct Client {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl Client {
+    pub fn new(url: &str) -> Self {
+        Client {
+            http: reqwest::Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub async fn get_status(&self) -> Result<GetStatusResponse> {
+        let resp = self
+            .http
+            .get(format!("{}/api/v1/status", self.url))
+            .send()
+            .await;
+        self.parse(resp).await
+    }
+
+    pub async fn get_serial_ports(&self) -> Result<GetSerialPortsResponse> {
+        let resp = self
+            .http
+            .get(format!("{}/api/v1/serial/ports", self.url))
+            .send()
+            .await;
+        self.parse(resp).await
+    }
+
+    pub async fn post_power(&self, req: &PostPowerRequest) -> Result<()> {
+        self.post("/api/v1/power", req).await
+    }
+
+    pub async fn post_source(&self, req: &PostSourceRequest) -> Result<()> {
+        self.post("/api/v1/source", req).await
+    }
+
+    async fn post<T: Serialize>(&self, path: &str, req: &T) -> Result<()> {
+        let resp = self
+            .http
+            .post(format!("{}{path}", self.url))
+            .json(req)
+            .send()
+            .await;
+        let _: EmptyResponse = self.parse(resp).await?;
+        Ok(())
+    }
+
+    async fn parse<T: DeserializeOwned>(
+        &self,
+        resp: reqwest::Result<reqwest::Response>,
+    ) -> Result<T> {
+        let resp = resp.context(format!("request to {} failed", self.url))?;
+        let status = resp.status();
+        let body = resp
+            .bytes()
+            .await
+            .context(format!("failed to read response, status {status}"))?;
+        parse_body(status, &body)
+    }
+}
+
+/// Routes report failures as an `ErrorResponse` body, not only through the
+/// status code, so a successful body made of just a `message` is an error too.
+fn parse_body<T: DeserializeOwned>(status: StatusCode, body: &[u8]) -> Result<T> {
+    let body: Value =
+        serde_json::from_slice(body).context(format!("invalid response, status {status}"))?;
+    let is_error_shape = body
+        .as_object()
+        .is_some_and(|fields| fields.len() == 1 && fields.contains_key("message"));
+    if !status.is_success() || is_error_shape {
+        return match serde_json::from_value::<ErrorResponse>(body) {
+            Ok(e) if is_error_shape => Err(anyhow!("{}", e.message)),
+            _ => Err(anyhow!("request failed, status {status}")),
+        };
+    }
+    serde_json::from_value(body).context("invalid response")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::epson_codec::Power;
+
+    use super::*;
+
+    #[test]
+    pub fn test_parse_body() {
+        let req: PostPowerRequest = parse_body(StatusCode::OK, br#"{"power":"on"}"#).unwrap();
+        assert_eq!(Power::On, req.power);
+
+        let e = parse_body::<PostPowerRequest>(StatusCode::OK, br#"{"message":"timed out"}"#)
+            .unwrap_err();
+        assert_eq!("timed out", format!("{e}"));
+
+        let e = parse_body::<PostPowerRequest>(StatusCode::BAD_GATEWAY, b"<html>bad gateway")
+            .unwrap_err();
+        assert_eq!("invalid response, status 502 Bad Gateway", format!("{e}"));
+
+        let e = parse_body::<EmptyResponse>(StatusCode::NOT_FOUND, b"{}").unwrap_err();
+        assert_eq!("request failed, status 404 Not Found", format!("{e}"));
+    }
+}
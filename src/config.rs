@@ -1,67 +1,705 @@
-use std::{env, str::FromStr, time::Duration};
+use std::{
+    env, fs,
+    num::NonZeroU64,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
-use crate::logger::init_logger;
+use crate::{cli::ConfigArgs, logger::init_logger};
 use anyhow::{anyhow, Context, Result};
-use log::debug;
+use log::{debug, info, warn};
+use serde::{Deserialize, Deserializer, Serialize};
+use serialport::{SerialPortInfo, SerialPortType};
+use utoipa::ToSchema;
+
+/// The Raspberry Pi console UART, never connected to the projector.
+const DEFAULT_SERIAL_EXCLUDE: &str = "/dev/ttyAMA0";
 
 pub struct Config {
     pub http_port: u16,
     pub serial_port: String,
+    pub serial_port_selector: SerialPortSelector,
     pub read_timeout: Duration,
+    pub poll_interval: Duration,
+    pub raw_commands_enabled: bool,
+    pub escvpnet_port: Option<u16>,
+    pub escvpnet_password: Option<String>,
+    pub pjlink_port: Option<u16>,
+    pub pjlink_password: Option<String>,
+    pub pjlink_name: String,
+    pub pjlink_mac_address: Option<String>,
+    pub mqtt_host: Option<String>,
+    pub mqtt_port: u16,
+    pub mqtt_username: Option<String>,
+    pub mqtt_password: Option<String>,
+    pub mqtt_topic: String,
+    pub mqtt_discovery_prefix: String,
+}
+
+/// Settings read from the YAML file given with `--config` or `CONFIG_FILE`.
+/// Every key is optional and overridden by its environment variable.
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    log_level: Option<LogLevel>,
+    http_port: Option<u16>,
+    serial_port: Option<String>,
+    serial: SerialConfigFile,
+    /// Seconds to wait for a reply from the projector.
+    timeout: Option<u64>,
+    /// Seconds between status polls.
+    poll_interval: Option<NonZeroU64>,
+    raw_commands_enabled: Option<bool>,
+    escvpnet: EscvpnetConfigFile,
+    pjlink: PjlinkConfigFile,
+    mqtt: MqttConfigFile,
+}
+
+/// Parsed while reading the config file so a bad level is reported against
+/// the `log_level` key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LogLevel(log::LevelFilter);
+
+impl FromStr for LogLevel {
+    type Err = log::ParseLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        log::LevelFilter::from_str(s).map(LogLevel)
+    }
+}
+
+impl<'de> Deserialize<'de> for LogLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct SerialConfigFile {
+    usb_vid: Option<UsbId>,
+    usb_pid: Option<UsbId>,
+    usb_serial_number: Option<String>,
+    exclude: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct EscvpnetConfigFile {
+    port: Option<u16>,
+    password: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct PjlinkConfigFile {
+    port: Option<u16>,
+    password: Option<String>,
+    name: Option<String>,
+    mac_address: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct MqttConfigFile {
+    host: Option<String>,
+    port: Option<u16>,
+    username: Option<String>,
+    password: Option<String>,
+    topic: Option<String>,
+    discovery_prefix: Option<String>,
 }
 
 impl Config {
-    pub fn new() -> Result<Config> {
-        let log_level = env::var("LOG_LEVEL").unwrap_or("info".to_string());
-        let log_level = log::LevelFilter::from_str(&log_level)?;
+    /// Command line arguments take precedence over environment variables, which
+    /// take precedence over the config file.
+    pub fn new(args: &ConfigArgs, default_log_level: &str) -> Result<Config> {
+        let file = ConfigFile::from_args(args)?;
+
+        let default_log_level = LogLevel::from_str(default_log_level)?;
+        let LogLevel(log_level) = setting("LOG_LEVEL", file.log_level, default_log_level)?;
         init_logger(log_level)?;
 
-        let http_port = env::var("HTTP_PORT").unwrap_or("8080".to_string());
-        let http_port = http_port
-            .parse::<u16>()
-            .context(format!("invalid HTTP_PORT {http_port}"))?;
+        let http_port = match args.http_port {
+            Some(http_port) => http_port,
+            None => setting("HTTP_PORT", file.http_port, 8080)?,
+        };
+        let timeout = match args.timeout {
+            Some(timeout) => timeout,
+            None => setting("TIMEOUT", file.timeout, 3)?,
+        };
+        let poll_interval = setting(
+            "POLL_INTERVAL",
+            file.poll_interval,
+            NonZeroU64::new(5).unwrap(),
+        )?;
+        let raw_commands_enabled =
+            setting("RAW_COMMANDS_ENABLED", file.raw_commands_enabled, false)?;
+
+        let escvpnet_port = optional_setting("ESCVPNET_PORT", file.escvpnet.port)?;
+        let escvpnet_password = optional_setting("ESCVPNET_PASSWORD", file.escvpnet.password)?;
 
-        let timeout = env::var("TIMEOUT").unwrap_or("3".to_string());
-        let timeout = timeout
-            .parse::<u64>()
-            .context(format!("invalid TIMEOUT {timeout}"))?;
+        let pjlink_port = optional_setting("PJLINK_PORT", file.pjlink.port)?;
+        let pjlink_password = optional_setting("PJLINK_PASSWORD", file.pjlink.password)?;
+        let pjlink_name = setting("PJLINK_NAME", file.pjlink.name, "epson".to_string())?;
+        let pjlink_mac_address = optional_setting("PJLINK_MAC_ADDRESS", file.pjlink.mac_address)?;
 
-        let serial_port = find_serial_port()?;
+        let mqtt_host = optional_setting("MQTT_HOST", file.mqtt.host)?;
+        let mqtt_port = setting("MQTT_PORT", file.mqtt.port, 1883)?;
+        let mqtt_username = optional_setting("MQTT_USERNAME", file.mqtt.username)?;
+        let mqtt_password = optional_setting("MQTT_PASSWORD", file.mqtt.password)?;
+        let mqtt_topic = setting("MQTT_TOPIC", file.mqtt.topic, "epson-projector".to_string())?;
+        let mqtt_discovery_prefix = setting(
+            "MQTT_DISCOVERY_PREFIX",
+            file.mqtt.discovery_prefix,
+            "homeassistant".to_string(),
+        )?;
+
+        let serial_port_selector =
+            SerialPortSelector::from_settings(args, file.serial_port, file.serial)?;
+        let serial_port = match &serial_port_selector.serial_port {
+            Some(serial_port) => {
+                log_configured_serial_port(serial_port);
+                serial_port.clone()
+            }
+            None => find_serial_port(&serial_port_selector)?,
+        };
 
         Ok(Config {
             http_port,
             serial_port,
+            serial_port_selector,
             read_timeout: Duration::from_secs(timeout),
+            poll_interval: Duration::from_secs(poll_interval.get()),
+            raw_commands_enabled,
+            escvpnet_port,
+            escvpnet_password,
+            pjlink_port,
+            pjlink_password,
+            pjlink_name,
+            pjlink_mac_address,
+            mqtt_host,
+            mqtt_port,
+            mqtt_username,
+            mqtt_password,
+            mqtt_topic,
+            mqtt_discovery_prefix,
         })
     }
+
+    /// How long since the projector last answered before the bridge counts as
+    /// not ready. The monitor talks to the projector every poll, allow a couple
+    /// to fail.
+    pub fn ready_max_age(&self) -> Duration {
+        self.poll_interval * 3 + self.read_timeout
+    }
+
+    /// How long since the monitor last finished a poll before the bridge
+    /// counts as hung. A details poll sends five commands that may each time
+    /// out, allow for as many from clients queued ahead of them.
+    pub fn poll_max_age(&self) -> Duration {
+        self.poll_interval + self.read_timeout * 10
+    }
+}
+
+impl ConfigFile {
+    fn from_args(args: &ConfigArgs) -> Result<ConfigFile> {
+        match &args.config {
+            Some(path) => ConfigFile::load(path),
+            None => match env::var("CONFIG_FILE") {
+                Ok(path) => ConfigFile::load(Path::new(&path)),
+                Err(_) => Ok(ConfigFile::default()),
+            },
+        }
+    }
+
+    fn load(path: &Path) -> Result<ConfigFile> {
+        let contents =
+            fs::read_to_string(path).context(format!("failed to read config file {path:?}"))?;
+        ConfigFile::parse(&contents).context(format!("invalid config file {path:?}"))
+    }
+
+    /// Errors name the offending key, e.g. `mqtt.port: invalid type: ...`.
+    fn parse(contents: &str) -> Result<ConfigFile> {
+        if contents.trim().is_empty() {
+            return Ok(ConfigFile::default());
+        }
+        let deserializer = serde_yml::Deserializer::from_str(contents);
+        serde_path_to_error::deserialize(deserializer).map_err(|e| {
+            let path = e.path().to_string();
+            // serde_yml already names the key in some errors
+            let message = e.into_inner().to_string();
+            if message.starts_with(&format!("{path}: ")) {
+                anyhow!("{message}")
+            } else {
+                anyhow!("{path}: {message}")
+            }
+        })
+    }
+}
+
+/// How the serial port is picked, either configured by name or searched for
+/// among the ports that are not excluded and match the USB filters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SerialPortSelector {
+    /// Set with `--serial-port`, `SERIAL_PORT` or the config file, the other
+    /// fields are ignored when set.
+    pub serial_port: Option<String>,
+    pub usb_vid: Option<u16>,
+    pub usb_pid: Option<u16>,
+    pub usb_serial_number: Option<String>,
+    pub exclude: Vec<String>,
+}
+
+impl SerialPortSelector {
+    /// The selector from the command line, environment and config file
+    /// without the rest of the config.
+    pub fn from_args(args: &ConfigArgs) -> Result<SerialPortSelector> {
+        let file = ConfigFile::from_args(args)?;
+        SerialPortSelector::from_settings(args, file.serial_port, file.serial)
+    }
+
+    fn from_settings(
+        args: &ConfigArgs,
+        serial_port: Option<String>,
+        file: SerialConfigFile,
+    ) -> Result<SerialPortSelector> {
+        let serial_port = match &args.serial_port {
+            Some(serial_port) => Some(serial_port.clone()),
+            None => optional_setting("SERIAL_PORT", serial_port)?,
+        };
+        let usb_vid = optional_setting("SERIAL_USB_VID", file.usb_vid)?;
+        let usb_pid = optional_setting("SERIAL_USB_PID", file.usb_pid)?;
+        Ok(SerialPortSelector {
+            serial_port,
+            usb_vid: usb_vid.map(|UsbId(vid)| vid),
+            usb_pid: usb_pid.map(|UsbId(pid)| pid),
+            usb_serial_number: optional_setting(
+                "SERIAL_USB_SERIAL_NUMBER",
+                file.usb_serial_number,
+            )?,
+            exclude: list_setting(
+                "SERIAL_EXCLUDE",
+                file.exclude,
+                vec![DEFAULT_SERIAL_EXCLUDE.to_string()],
+            ),
+        })
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.serial_port.is_some()
+    }
+
+    fn has_usb_filter(&self) -> bool {
+        self.usb_vid.is_some() || self.usb_pid.is_some() || self.usb_serial_number.is_some()
+    }
+
+    /// Ports other than USB only match when no USB filter is set.
+    fn matches_usb_filter(&self, info: &SerialPortInfo) -> bool {
+        if !self.has_usb_filter() {
+            return true;
+        }
+        let SerialPortType::UsbPort(usb) = &info.port_type else {
+            return false;
+        };
+        self.usb_vid.is_none_or(|vid| vid == usb.vid)
+            && self.usb_pid.is_none_or(|pid| pid == usb.pid)
+            && self
+                .usb_serial_number
+                .as_ref()
+                .is_none_or(|serial_number| usb.serial_number.as_ref() == Some(serial_number))
+    }
+}
+
+/// USB vendor or product ID in hex, `067b` as well as `0x067b`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct UsbId(u16);
+
+impl FromStr for UsbId {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim_start_matches("0x").trim_start_matches("0X");
+        u16::from_str_radix(hex, 16).map(UsbId)
+    }
+}
+
+impl<'de> Deserialize<'de> for UsbId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // YAML reads unquoted 2303 as decimal and 0x067b as 1659, neither is
+        // what was meant, so only strings are accepted
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Text(String),
+            Number(u64),
+        }
+        match Value::deserialize(deserializer)? {
+            Value::Text(text) => text.parse().map_err(serde::de::Error::custom),
+            Value::Number(number) => Err(serde::de::Error::custom(format!(
+                "USB IDs are hex and must be quoted, e.g. \"067b\", not read as the number {number}"
+            ))),
+        }
+    }
+}
+
+/// Comma separated in the environment variable, a list in the config file.
+fn list_setting(name: &str, file_value: Option<Vec<String>>, default: Vec<String>) -> Vec<String> {
+    match env::var(name) {
+        Ok(value) => value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => file_value.unwrap_or(default),
+    }
+}
+
+/// The environment variable if set, otherwise the config file value, otherwise
+/// the default.
+fn setting<T>(name: &str, file_value: Option<T>, default: T) -> Result<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    Ok(optional_setting(name, file_value)?.unwrap_or(default))
 }
 
-fn find_serial_port() -> Result<String> {
-    if let Ok(serial_port) = env::var("SERIAL_PORT") {
-        return Ok(serial_port);
+fn optional_setting<T>(name: &str, file_value: Option<T>) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    match env::var(name) {
+        Ok(value) => Ok(Some(
+            value
+                .parse::<T>()
+                .context(format!("invalid {name} {value}"))?,
+        )),
+        Err(_) => Ok(file_value),
     }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SerialPortSelection {
+    /// Matches the configured serial port.
+    Configured,
+    /// A serial port is configured and this is not it.
+    NotConfigured,
+    /// The only port left after exclusions and USB filters, picked
+    /// automatically.
+    OnlyCandidate,
+    /// Several ports are left after exclusions and USB filters so none is
+    /// picked.
+    Ambiguous,
+    /// In the exclude list, e.g. the Raspberry Pi console UART.
+    Excluded,
+    /// Does not match the USB vendor/product ID or serial number filter.
+    NotMatched,
+}
+
+impl SerialPortSelection {
+    pub fn is_selected(&self) -> bool {
+        matches!(
+            self,
+            SerialPortSelection::Configured | SerialPortSelection::OnlyCandidate
+        )
+    }
+}
+
+/// A serial port found on this machine and whether the bridge would use it.
+pub struct SerialPortCandidate {
+    pub info: SerialPortInfo,
+    pub selection: SerialPortSelection,
+}
+
+/// Every serial port on this machine, classified the same way
+/// `find_serial_port` picks one.
+pub fn list_serial_ports(selector: &SerialPortSelector) -> Result<Vec<SerialPortCandidate>> {
+    let ports = serialport::available_ports()?;
+    Ok(classify_serial_ports(ports, selector))
+}
 
-    debug!("SERIAL_PORT environment variable not set, searching for serial port");
-    let mut found_port = None;
-    let available_ports = serialport::available_ports()?;
-    for available_port in available_ports {
-        if available_port.port_name == "/dev/ttyAMA0" {
-            continue;
+fn classify_serial_ports(
+    ports: Vec<SerialPortInfo>,
+    selector: &SerialPortSelector,
+) -> Vec<SerialPortCandidate> {
+    let filtered = |info: &SerialPortInfo| {
+        if selector.exclude.contains(&info.port_name) {
+            Some(SerialPortSelection::Excluded)
+        } else if !selector.matches_usb_filter(info) {
+            Some(SerialPortSelection::NotMatched)
+        } else {
+            None
         }
-        debug!("available_port: {available_port:?}");
-        if found_port.is_some() {
-            return Err(anyhow!(
-                "SERIAL_PORT not set and multiple serial ports were found"
-            ));
+    };
+    let candidates = ports.iter().filter(|info| filtered(info).is_none()).count();
+    let configured = selector.serial_port.as_deref().map(canonical_port_name);
+    ports
+        .into_iter()
+        .map(|info| {
+            let selection = match &configured {
+                Some(configured) if canonical_port_name(&info.port_name) == *configured => {
+                    SerialPortSelection::Configured
+                }
+                Some(_) => SerialPortSelection::NotConfigured,
+                None => match filtered(&info) {
+                    Some(selection) => selection,
+                    None if candidates == 1 => SerialPortSelection::OnlyCandidate,
+                    None => SerialPortSelection::Ambiguous,
+                },
+            };
+            SerialPortCandidate { info, selection }
+        })
+        .collect()
+}
+
+/// Resolves symlinks such as `/dev/serial/by-id/...` so they match the device
+/// name reported by the port listing.
+fn canonical_port_name(port_name: &str) -> PathBuf {
+    fs::canonicalize(port_name).unwrap_or_else(|_| PathBuf::from(port_name))
+}
+
+/// Logs the USB details of a configured port, which are only known from the
+/// port listing.
+fn log_configured_serial_port(serial_port: &str) {
+    let ports = match serialport::available_ports() {
+        Ok(ports) => ports,
+        Err(e) => {
+            warn!("failed to list serial ports; error = {e}");
+            return;
         }
-        found_port = Some(available_port.port_name);
+    };
+    let canonical = canonical_port_name(serial_port);
+    match ports
+        .iter()
+        .find(|info| canonical_port_name(&info.port_name) == canonical)
+    {
+        Some(info) => log_serial_port_info("configured", info),
+        None => info!("configured serial port {serial_port} is not in the port listing"),
     }
+}
+
+fn log_serial_port_info(how: &str, info: &SerialPortInfo) {
+    match &info.port_type {
+        SerialPortType::UsbPort(usb) => info!("{how} serial port {}; {usb:?}", info.port_name),
+        port_type => info!("{how} serial port {}; {port_type:?}", info.port_name),
+    }
+}
 
-    if let Some(found_port) = found_port {
-        return Ok(found_port);
+pub fn find_serial_port(selector: &SerialPortSelector) -> Result<String> {
+    debug!("serial port not configured, searching for serial port; {selector:?}");
+    let ports = list_serial_ports(selector)?;
+    for port in &ports {
+        debug!("available_port: {:?}; {:?}", port.info, port.selection);
+    }
+
+    if let Some(port) = ports.iter().find(|port| port.selection.is_selected()) {
+        log_serial_port_info("found", &port.info);
+        return Ok(port.info.port_name.clone());
+    }
+    if ports
+        .iter()
+        .any(|port| port.selection == SerialPortSelection::Ambiguous)
+    {
+        return Err(anyhow!(
+            "SERIAL_PORT not set and multiple serial ports were found, set SERIAL_PORT or SERIAL_USB_VID, SERIAL_USB_PID or SERIAL_USB_SERIAL_NUMBER"
+        ));
+    }
+    if selector.has_usb_filter() {
+        return Err(anyhow!(
+            "could not find a serial port matching SERIAL_USB_VID, SERIAL_USB_PID and SERIAL_USB_SERIAL_NUMBER"
+        ));
     }
 
     Err(anyhow!(
         "could not find available port and SERIAL_PORT is not set"
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse_config_file() {
+        let file = ConfigFile::parse(
+            "http_port: 80\nserial_port: /dev/ttyUSB0\npjlink:\n  port: 4352\n  name: theater\n",
+        )
+        .unwrap();
+        assert_eq!(Some(80), file.http_port);
+        assert_eq!(Some("/dev/ttyUSB0".to_string()), file.serial_port);
+        assert_eq!(Some(4352), file.pjlink.port);
+        assert_eq!(Some("theater".to_string()), file.pjlink.name);
+        assert_eq!(None, file.mqtt.host);
+
+        assert_eq!(ConfigFile::default(), ConfigFile::parse("").unwrap());
+
+        let file = ConfigFile::parse(
+            "serial:\n  usb_vid: \"067b\"\n  usb_pid: \"0x2303\"\n  exclude:\n    - /dev/ttyAMA0\n    - /dev/ttyS0\n",
+        )
+        .unwrap();
+        assert_eq!(Some(UsbId(0x067b)), file.serial.usb_vid);
+        assert_eq!(Some(UsbId(0x2303)), file.serial.usb_pid);
+        assert_eq!(
+            Some(vec!["/dev/ttyAMA0".to_string(), "/dev/ttyS0".to_string()]),
+            file.serial.exclude
+        );
+    }
+
+    fn port(name: &str) -> SerialPortInfo {
+        SerialPortInfo {
+            port_name: name.to_string(),
+            port_type: SerialPortType::Unknown,
+        }
+    }
+
+    fn usb_port(name: &str, vid: u16, pid: u16, serial_number: &str) -> SerialPortInfo {
+        SerialPortInfo {
+            port_name: name.to_string(),
+            port_type: SerialPortType::UsbPort(serialport::UsbPortInfo {
+                vid,
+                pid,
+                serial_number: Some(serial_number.to_string()),
+                manufacturer: None,
+                product: None,
+                interface: None,
+            }),
+        }
+    }
+
+    #[test]
+    pub fn test_parse_usb_id() {
+        assert_eq!(UsbId(0x067b), "067b".parse().unwrap());
+        assert_eq!(UsbId(0x2303), "0x2303".parse().unwrap());
+        assert!("pl2303".parse::<UsbId>().is_err());
+
+        let e = ConfigFile::parse("serial:\n  usb_pid: pl2303\n").unwrap_err();
+        assert!(format!("{e}").starts_with("serial.usb_pid: "), "{e}");
+
+        let e = ConfigFile::parse("serial:\n  usb_vid: 0x067b\n").unwrap_err();
+        assert!(format!("{e}").contains("must be quoted"), "{e}");
+
+        let e = ConfigFile::parse("serial:\n  usb_pid: 2303\n").unwrap_err();
+        assert!(format!("{e}").contains("must be quoted"), "{e}");
+    }
+
+    #[test]
+    pub fn test_classify_serial_ports() {
+        let selections = |ports: Vec<SerialPortInfo>, selector: &SerialPortSelector| {
+            classify_serial_ports(ports, selector)
+                .into_iter()
+                .map(|port| port.selection)
+                .collect::<Vec<_>>()
+        };
+        let search = SerialPortSelector {
+            exclude: vec![DEFAULT_SERIAL_EXCLUDE.to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![
+                SerialPortSelection::Excluded,
+                SerialPortSelection::OnlyCandidate
+            ],
+            selections(vec![port("/dev/ttyAMA0"), port("/dev/ttyUSB0")], &search)
+        );
+        assert_eq!(
+            vec![
+                SerialPortSelection::Ambiguous,
+                SerialPortSelection::Ambiguous
+            ],
+            selections(vec![port("/dev/ttyUSB0"), port("/dev/ttyUSB1")], &search)
+        );
+
+        let configured = SerialPortSelector {
+            serial_port: Some("/dev/ttyUSB1".to_string()),
+            ..search.clone()
+        };
+        assert_eq!(
+            vec![
+                SerialPortSelection::NotConfigured,
+                SerialPortSelection::Configured
+            ],
+            selections(
+                vec![port("/dev/ttyUSB0"), port("/dev/ttyUSB1")],
+                &configured
+            )
+        );
+
+        let dir = env::temp_dir().join(format!("serial-by-id-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let device = dir.join("ttyUSB7");
+        let link = dir.join("usb-Prolific_PL2303-if00-port0");
+        fs::write(&device, "").unwrap();
+        std::os::unix::fs::symlink(&device, &link).unwrap();
+        let by_id = SerialPortSelector {
+            serial_port: Some(link.to_string_lossy().to_string()),
+            ..search.clone()
+        };
+        assert_eq!(
+            vec![
+                SerialPortSelection::NotConfigured,
+                SerialPortSelection::Configured
+            ],
+            selections(
+                vec![port("/dev/ttyUSB0"), port(&device.to_string_lossy())],
+                &by_id
+            )
+        );
+        fs::remove_dir_all(&dir).unwrap();
+
+        let ports = vec![
+            port("/dev/ttyS0"),
+            usb_port("/dev/ttyUSB0", 0x0403, 0x6001, "FT1"),
+            usb_port("/dev/ttyUSB1", 0x067b, 0x2303, "PL1"),
+            usb_port("/dev/ttyUSB2", 0x067b, 0x2303, "PL2"),
+        ];
+        let by_vid_pid = SerialPortSelector {
+            usb_vid: Some(0x067b),
+            usb_pid: Some(0x2303),
+            ..search.clone()
+        };
+        assert_eq!(
+            vec![
+                SerialPortSelection::NotMatched,
+                SerialPortSelection::NotMatched,
+                SerialPortSelection::Ambiguous,
+                SerialPortSelection::Ambiguous
+            ],
+            selections(ports.clone(), &by_vid_pid)
+        );
+        let by_serial_number = SerialPortSelector {
+            usb_serial_number: Some("PL2".to_string()),
+            ..by_vid_pid
+        };
+        assert_eq!(
+            vec![
+                SerialPortSelection::NotMatched,
+                SerialPortSelection::NotMatched,
+                SerialPortSelection::NotMatched,
+                SerialPortSelection::OnlyCandidate
+            ],
+            selections(ports, &by_serial_number)
+        );
+    }
+
+    #[test]
+    pub fn test_parse_config_file_errors() {
+        let e = ConfigFile::parse("mqtt:\n  port: mqtt.local\n").unwrap_err();
+        assert!(format!("{e}").starts_with("mqtt.port: "), "{e}");
+
+        let e = ConfigFile::parse("pjlink:\n  passwrd: secret\n").unwrap_err();
+        assert!(format!("{e}").starts_with("pjlink.passwrd: "), "{e}");
+
+        let e = ConfigFile::parse("poll_interval: 0\n").unwrap_err();
+        assert!(format!("{e}").starts_with("poll_interval: "), "{e}");
+
+        let e = ConfigFile::parse("log_level: loud\n").unwrap_err();
+        assert!(format!("{e}").starts_with("log_level: "), "{e}");
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::Write;
+use std::{fmt::Write, str::FromStr};
 
 use anyhow::{anyhow, Context, Result};
 use bytes::{Buf, BytesMut};
@@ -9,11 +9,43 @@ use serde::{Deserialize, Serialize};
 use tokio_util::codec::{Decoder, Encoder};
 use utoipa::ToSchema;
 
-pub struct EpsonCodec {}
+pub struct EpsonCodec {
+    /// Set while a `EpsonInput::Raw` command is outstanding so its reply is
+    /// returned verbatim instead of being parsed.
+    raw_response: bool,
+}
 
 impl EpsonCodec {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            raw_response: false,
+        }
+    }
+
+    fn decode_raw(&mut self, src: &mut BytesMut) -> Result<Option<EpsonOutput>> {
+        // commands without a reply only answer with the ":" prompt
+        if src.starts_with(b":") {
+            src.advance(b":".len());
+            self.raw_response = false;
+            return Ok(Some(EpsonOutput::Raw(String::new())));
+        }
+
+        let offset = src.iter().position(|b| *b == b'\r');
+        if let Some(offset) = offset {
+            let line = src.split_to(offset);
+            self.raw_response = false;
+            debug!("received raw line {line:?}");
+            if line == "ERR" {
+                Ok(Some(EpsonOutput::Error))
+            } else {
+                match std::str::from_utf8(&line) {
+                    Ok(str) => Ok(Some(EpsonOutput::Raw(str.to_string()))),
+                    Err(e) => Err(anyhow!("failed to decode raw line {line:?}; error = {e}")),
+                }
+            }
+        } else {
+            Ok(None)
+        }
     }
 
     fn parse_power_status(line: &mut BytesMut) -> Result<EpsonOutput> {
@@ -34,6 +66,140 @@ impl EpsonCodec {
         }
     }
 
+    fn parse_error_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"ERR=".len());
+        let code = EpsonCodec::parse_u8(line)?;
+        match ProjectorError::from_u8(code) {
+            Some(error) => Ok(EpsonOutput::ErrorStatus(error)),
+            None => Err(anyhow!("unknown error status: {code}")),
+        }
+    }
+
+    fn parse_volume_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"VOL=".len());
+        let volume = EpsonCodec::parse_decimal(line)?;
+        Ok(EpsonOutput::VolumeStatus(volume))
+    }
+
+    fn parse_mute_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"MUTE=".len());
+        let mute = EpsonCodec::parse_on_off(line)?;
+        Ok(EpsonOutput::MuteStatus(mute))
+    }
+
+    fn parse_mute_color_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"MSEL=".len());
+        let code = EpsonCodec::parse_u8(line)?;
+        match MuteColor::from_u8(code) {
+            Some(mute_color) => Ok(EpsonOutput::MuteColorStatus(mute_color)),
+            None => Err(anyhow!("unknown mute color: {code}")),
+        }
+    }
+
+    fn parse_lamp_hours(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"LAMP=".len());
+        let hours = EpsonCodec::parse_decimal(line)?;
+        Ok(EpsonOutput::LampHours(hours))
+    }
+
+    fn parse_filter_hours(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"FILTER=".len());
+        let hours = EpsonCodec::parse_decimal(line)?;
+        Ok(EpsonOutput::FilterHours(hours))
+    }
+
+    fn parse_color_mode_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"CMODE=".len());
+        let code = EpsonCodec::parse_u8(line)?;
+        match ColorMode::from_u8(code) {
+            Some(color_mode) => Ok(EpsonOutput::ColorModeStatus(color_mode)),
+            None => Err(anyhow!("unknown color mode: {code}")),
+        }
+    }
+
+    fn parse_picture_setting_status(
+        line: &mut BytesMut,
+        setting: PictureSetting,
+    ) -> Result<EpsonOutput> {
+        line.advance(setting.command().len() + b"=".len());
+        let value = EpsonCodec::parse_decimal(line)?;
+        Ok(EpsonOutput::PictureSettingStatus(setting, value))
+    }
+
+    fn parse_aspect_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"ASPECT=".len());
+        let code = EpsonCodec::parse_u8(line)?;
+        match Aspect::from_u8(code) {
+            Some(aspect) => Ok(EpsonOutput::AspectStatus(aspect)),
+            None => Err(anyhow!("unknown aspect: {code}")),
+        }
+    }
+
+    fn parse_three_d_mode_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"3DMODE=".len());
+        let code = EpsonCodec::parse_u8(line)?;
+        match ThreeDMode::from_u8(code) {
+            Some(mode) => Ok(EpsonOutput::ThreeDModeStatus(mode)),
+            None => Err(anyhow!("unknown 3D mode: {code}")),
+        }
+    }
+
+    fn parse_three_d_format_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"3DFORMAT=".len());
+        let code = EpsonCodec::parse_u8(line)?;
+        match ThreeDFormat::from_u8(code) {
+            Some(format) => Ok(EpsonOutput::ThreeDFormatStatus(format)),
+            None => Err(anyhow!("unknown 3D format: {code}")),
+        }
+    }
+
+    fn parse_three_d_brightness_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"3DBRIGHT=".len());
+        let code = EpsonCodec::parse_u8(line)?;
+        match ThreeDBrightness::from_u8(code) {
+            Some(brightness) => Ok(EpsonOutput::ThreeDBrightnessStatus(brightness)),
+            None => Err(anyhow!("unknown 3D brightness: {code}")),
+        }
+    }
+
+    fn parse_freeze_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"FREEZE=".len());
+        let freeze = EpsonCodec::parse_on_off(line)?;
+        Ok(EpsonOutput::FreezeStatus(freeze))
+    }
+
+    fn parse_horizontal_reverse_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"HREVERSE=".len());
+        let reverse = EpsonCodec::parse_on_off(line)?;
+        Ok(EpsonOutput::HorizontalReverseStatus(reverse))
+    }
+
+    fn parse_vertical_reverse_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"VREVERSE=".len());
+        let reverse = EpsonCodec::parse_on_off(line)?;
+        Ok(EpsonOutput::VerticalReverseStatus(reverse))
+    }
+
+    fn parse_serial_number_status(line: &mut BytesMut) -> Result<EpsonOutput> {
+        line.advance(b"SNO=".len());
+        match std::str::from_utf8(line) {
+            Ok(serial_number) => Ok(EpsonOutput::SerialNumberStatus(
+                serial_number.trim().to_string(),
+            )),
+            Err(e) => Err(anyhow!(
+                "failed to parse serial number {line:?}; error = {e}"
+            )),
+        }
+    }
+
+    fn parse_on_off(line: &mut BytesMut) -> Result<bool> {
+        match &line[..] {
+            b"ON" => Ok(true),
+            b"OFF" => Ok(false),
+            _ => Err(anyhow!("failed to parse on/off {line:?}")),
+        }
+    }
+
     fn parse_u8(line: &mut BytesMut) -> Result<u8> {
         let code = line.split_to(2);
         match std::str::from_utf8(&code) {
@@ -43,6 +209,18 @@ impl EpsonCodec {
         }
     }
 
+    fn parse_decimal<T: FromStr>(line: &mut BytesMut) -> Result<T> {
+        match std::str::from_utf8(line) {
+            Ok(value) => {
+                let value = value.split_whitespace().next().unwrap_or_default();
+                value
+                    .parse::<T>()
+                    .map_err(|_| anyhow!("failed to convert {value} to decimal"))
+            }
+            Err(e) => Err(anyhow!("failed to parse decimal {line:?}; error = {e}")),
+        }
+    }
+
     fn write_line(dst: &mut BytesMut, line: &str) -> Result<()> {
         debug!("writing line: \"{line}\"");
         dst.write_str(line)
@@ -63,6 +241,70 @@ impl EpsonCodec {
         EpsonCodec::write_line(dst, "SOURCE?")
     }
 
+    fn write_query_error(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "ERR?")
+    }
+
+    fn write_query_volume(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "VOL?")
+    }
+
+    fn write_query_mute(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "MUTE?")
+    }
+
+    fn write_query_mute_color(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "MSEL?")
+    }
+
+    fn write_query_lamp_hours(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "LAMP?")
+    }
+
+    fn write_query_filter_hours(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "FILTER?")
+    }
+
+    fn write_query_color_mode(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "CMODE?")
+    }
+
+    fn write_query_picture_setting(dst: &mut BytesMut, setting: PictureSetting) -> Result<()> {
+        EpsonCodec::write_line(dst, &format!("{}?", setting.command()))
+    }
+
+    fn write_query_aspect(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "ASPECT?")
+    }
+
+    fn write_query_three_d_mode(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "3DMODE?")
+    }
+
+    fn write_query_three_d_format(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "3DFORMAT?")
+    }
+
+    fn write_query_three_d_brightness(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "3DBRIGHT?")
+    }
+
+    fn write_query_freeze(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "FREEZE?")
+    }
+
+    fn write_query_horizontal_reverse(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "HREVERSE?")
+    }
+
+    fn write_query_vertical_reverse(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "VREVERSE?")
+    }
+
+    fn write_query_serial_number(dst: &mut BytesMut) -> Result<()> {
+        EpsonCodec::write_line(dst, "SNO?")
+    }
+
     fn write_set_power(dst: &mut BytesMut, power: Power) -> Result<()> {
         match power {
             Power::On => EpsonCodec::write_line(dst, "PWR ON"),
@@ -77,6 +319,95 @@ impl EpsonCodec {
         let cmd = format!("SOURCE {:02x}", source_value);
         EpsonCodec::write_line(dst, &cmd)
     }
+
+    fn write_set_volume(dst: &mut BytesMut, volume: u8) -> Result<()> {
+        EpsonCodec::write_line(dst, &format!("VOL {volume}"))
+    }
+
+    fn write_set_on_off(dst: &mut BytesMut, cmd: &str, on: bool) -> Result<()> {
+        if on {
+            EpsonCodec::write_line(dst, &format!("{cmd} ON"))
+        } else {
+            EpsonCodec::write_line(dst, &format!("{cmd} OFF"))
+        }
+    }
+
+    fn write_set_mute_color(dst: &mut BytesMut, mute_color: MuteColor) -> Result<()> {
+        let mute_color_value = mute_color
+            .to_u8()
+            .ok_or(anyhow!("invalid mute color: {mute_color:?}"))?;
+        let cmd = format!("MSEL {:02x}", mute_color_value);
+        EpsonCodec::write_line(dst, &cmd)
+    }
+
+    fn write_set_color_mode(dst: &mut BytesMut, color_mode: ColorMode) -> Result<()> {
+        let color_mode_value = color_mode
+            .to_u8()
+            .ok_or(anyhow!("invalid color mode: {color_mode:?}"))?;
+        let cmd = format!("CMODE {:02x}", color_mode_value);
+        EpsonCodec::write_line(dst, &cmd)
+    }
+
+    fn write_set_picture_setting(
+        dst: &mut BytesMut,
+        setting: PictureSetting,
+        value: u8,
+    ) -> Result<()> {
+        EpsonCodec::write_line(dst, &format!("{} {value}", setting.command()))
+    }
+
+    fn write_set_aspect(dst: &mut BytesMut, aspect: Aspect) -> Result<()> {
+        let aspect_value = aspect
+            .to_u8()
+            .ok_or(anyhow!("invalid aspect: {aspect:?}"))?;
+        let cmd = format!("ASPECT {:02x}", aspect_value);
+        EpsonCodec::write_line(dst, &cmd)
+    }
+
+    fn write_key(dst: &mut BytesMut, key: RemoteKey) -> Result<()> {
+        let key_value = key.to_u8().ok_or(anyhow!("invalid key: {key:?}"))?;
+        let cmd = format!("KEY {:02x}", key_value);
+        EpsonCodec::write_line(dst, &cmd)
+    }
+
+    fn write_set_three_d_mode(dst: &mut BytesMut, mode: ThreeDMode) -> Result<()> {
+        let mode_value = mode.to_u8().ok_or(anyhow!("invalid 3D mode: {mode:?}"))?;
+        let cmd = format!("3DMODE {:02x}", mode_value);
+        EpsonCodec::write_line(dst, &cmd)
+    }
+
+    fn write_set_three_d_format(dst: &mut BytesMut, format: ThreeDFormat) -> Result<()> {
+        let format_value = format
+            .to_u8()
+            .ok_or(anyhow!("invalid 3D format: {format:?}"))?;
+        let cmd = format!("3DFORMAT {:02x}", format_value);
+        EpsonCodec::write_line(dst, &cmd)
+    }
+
+    fn write_set_three_d_brightness(
+        dst: &mut BytesMut,
+        brightness: ThreeDBrightness,
+    ) -> Result<()> {
+        let brightness_value = brightness
+            .to_u8()
+            .ok_or(anyhow!("invalid 3D brightness: {brightness:?}"))?;
+        let cmd = format!("3DBRIGHT {:02x}", brightness_value);
+        EpsonCodec::write_line(dst, &cmd)
+    }
+
+    fn write_raw(dst: &mut BytesMut, cmd: &str) -> Result<()> {
+        if cmd.contains(['\r', '\n']) {
+            return Err(anyhow!("raw command must not contain CR or LF"));
+        }
+        EpsonCodec::write_line(dst, cmd)
+    }
+
+    fn write_adjust_volume(dst: &mut BytesMut, adjust: VolumeAdjust) -> Result<()> {
+        match adjust {
+            VolumeAdjust::Increase => EpsonCodec::write_line(dst, "VOL INC"),
+            VolumeAdjust::Decrease => EpsonCodec::write_line(dst, "VOL DEC"),
+        }
+    }
 }
 
 impl Decoder for EpsonCodec {
@@ -84,6 +415,10 @@ impl Decoder for EpsonCodec {
     type Error = anyhow::Error;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if self.raw_response {
+            return self.decode_raw(src);
+        }
+
         let offset = src.iter().position(|b| *b == b'\r');
         if let Some(offset) = offset {
             if offset == 0 {
@@ -100,6 +435,47 @@ impl Decoder for EpsonCodec {
                 Ok(Some(EpsonCodec::parse_power_status(&mut line)?))
             } else if line.starts_with(b"SOURCE=") {
                 Ok(Some(EpsonCodec::parse_source_status(&mut line)?))
+            } else if line.starts_with(b"ERR=") {
+                Ok(Some(EpsonCodec::parse_error_status(&mut line)?))
+            } else if line.starts_with(b"VOL=") {
+                Ok(Some(EpsonCodec::parse_volume_status(&mut line)?))
+            } else if line.starts_with(b"MUTE=") {
+                Ok(Some(EpsonCodec::parse_mute_status(&mut line)?))
+            } else if line.starts_with(b"MSEL=") {
+                Ok(Some(EpsonCodec::parse_mute_color_status(&mut line)?))
+            } else if line.starts_with(b"LAMP=") {
+                Ok(Some(EpsonCodec::parse_lamp_hours(&mut line)?))
+            } else if line.starts_with(b"FILTER=") {
+                Ok(Some(EpsonCodec::parse_filter_hours(&mut line)?))
+            } else if line.starts_with(b"CMODE=") {
+                Ok(Some(EpsonCodec::parse_color_mode_status(&mut line)?))
+            } else if let Some(setting) = PictureSetting::ALL
+                .into_iter()
+                .find(|setting| line.starts_with(format!("{}=", setting.command()).as_bytes()))
+            {
+                Ok(Some(EpsonCodec::parse_picture_setting_status(
+                    &mut line, setting,
+                )?))
+            } else if line.starts_with(b"ASPECT=") {
+                Ok(Some(EpsonCodec::parse_aspect_status(&mut line)?))
+            } else if line.starts_with(b"3DMODE=") {
+                Ok(Some(EpsonCodec::parse_three_d_mode_status(&mut line)?))
+            } else if line.starts_with(b"3DFORMAT=") {
+                Ok(Some(EpsonCodec::parse_three_d_format_status(&mut line)?))
+            } else if line.starts_with(b"3DBRIGHT=") {
+                Ok(Some(EpsonCodec::parse_three_d_brightness_status(
+                    &mut line,
+                )?))
+            } else if line.starts_with(b"FREEZE=") {
+                Ok(Some(EpsonCodec::parse_freeze_status(&mut line)?))
+            } else if line.starts_with(b"HREVERSE=") {
+                Ok(Some(EpsonCodec::parse_horizontal_reverse_status(
+                    &mut line,
+                )?))
+            } else if line.starts_with(b"VREVERSE=") {
+                Ok(Some(EpsonCodec::parse_vertical_reverse_status(&mut line)?))
+            } else if line.starts_with(b"SNO=") {
+                Ok(Some(EpsonCodec::parse_serial_number_status(&mut line)?))
             } else {
                 match std::str::from_utf8(&line) {
                     Ok(str) => Ok(Some(EpsonOutput::InvalidLine(str.to_string()))),
@@ -118,12 +494,60 @@ impl Encoder<EpsonInput> for EpsonCodec {
     type Error = anyhow::Error;
 
     fn encode(&mut self, item: EpsonInput, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.raw_response = matches!(item, EpsonInput::Raw(_));
         match item {
             EpsonInput::Noop => EpsonCodec::write_noop(dst),
             EpsonInput::QueryPower => EpsonCodec::write_query_power(dst),
             EpsonInput::QuerySource => EpsonCodec::write_query_source(dst),
             EpsonInput::SetPower(power) => EpsonCodec::write_set_power(dst, power),
             EpsonInput::SetSource(source) => EpsonCodec::write_set_source(dst, source),
+            EpsonInput::QueryError => EpsonCodec::write_query_error(dst),
+            EpsonInput::QueryVolume => EpsonCodec::write_query_volume(dst),
+            EpsonInput::SetVolume(volume) => EpsonCodec::write_set_volume(dst, volume),
+            EpsonInput::AdjustVolume(adjust) => EpsonCodec::write_adjust_volume(dst, adjust),
+            EpsonInput::QueryMute => EpsonCodec::write_query_mute(dst),
+            EpsonInput::SetMute(mute) => EpsonCodec::write_set_on_off(dst, "MUTE", mute),
+            EpsonInput::QueryMuteColor => EpsonCodec::write_query_mute_color(dst),
+            EpsonInput::SetMuteColor(mute_color) => {
+                EpsonCodec::write_set_mute_color(dst, mute_color)
+            }
+            EpsonInput::QueryLampHours => EpsonCodec::write_query_lamp_hours(dst),
+            EpsonInput::QueryFilterHours => EpsonCodec::write_query_filter_hours(dst),
+            EpsonInput::QueryColorMode => EpsonCodec::write_query_color_mode(dst),
+            EpsonInput::SetColorMode(color_mode) => {
+                EpsonCodec::write_set_color_mode(dst, color_mode)
+            }
+            EpsonInput::QueryPictureSetting(setting) => {
+                EpsonCodec::write_query_picture_setting(dst, setting)
+            }
+            EpsonInput::SetPictureSetting(setting, value) => {
+                EpsonCodec::write_set_picture_setting(dst, setting, value)
+            }
+            EpsonInput::QueryAspect => EpsonCodec::write_query_aspect(dst),
+            EpsonInput::SetAspect(aspect) => EpsonCodec::write_set_aspect(dst, aspect),
+            EpsonInput::Key(key) => EpsonCodec::write_key(dst, key),
+            EpsonInput::QueryThreeDMode => EpsonCodec::write_query_three_d_mode(dst),
+            EpsonInput::SetThreeDMode(mode) => EpsonCodec::write_set_three_d_mode(dst, mode),
+            EpsonInput::QueryThreeDFormat => EpsonCodec::write_query_three_d_format(dst),
+            EpsonInput::SetThreeDFormat(format) => {
+                EpsonCodec::write_set_three_d_format(dst, format)
+            }
+            EpsonInput::QueryThreeDBrightness => EpsonCodec::write_query_three_d_brightness(dst),
+            EpsonInput::SetThreeDBrightness(brightness) => {
+                EpsonCodec::write_set_three_d_brightness(dst, brightness)
+            }
+            EpsonInput::QueryFreeze => EpsonCodec::write_query_freeze(dst),
+            EpsonInput::SetFreeze(freeze) => EpsonCodec::write_set_on_off(dst, "FREEZE", freeze),
+            EpsonInput::QueryHorizontalReverse => EpsonCodec::write_query_horizontal_reverse(dst),
+            EpsonInput::SetHorizontalReverse(reverse) => {
+                EpsonCodec::write_set_on_off(dst, "HREVERSE", reverse)
+            }
+            EpsonInput::QueryVerticalReverse => EpsonCodec::write_query_vertical_reverse(dst),
+            EpsonInput::SetVerticalReverse(reverse) => {
+                EpsonCodec::write_set_on_off(dst, "VREVERSE", reverse)
+            }
+            EpsonInput::QuerySerialNumber => EpsonCodec::write_query_serial_number(dst),
+            EpsonInput::Raw(cmd) => EpsonCodec::write_raw(dst, &cmd),
         }
     }
 }
@@ -134,6 +558,23 @@ pub enum EpsonOutput {
     InvalidLine(String),
     PowerStatus(PowerStatus),
     SourceStatus(Source),
+    ErrorStatus(ProjectorError),
+    VolumeStatus(u8),
+    MuteStatus(bool),
+    MuteColorStatus(MuteColor),
+    LampHours(u32),
+    FilterHours(u32),
+    ColorModeStatus(ColorMode),
+    PictureSettingStatus(PictureSetting, u8),
+    AspectStatus(Aspect),
+    ThreeDModeStatus(ThreeDMode),
+    ThreeDFormatStatus(ThreeDFormat),
+    ThreeDBrightnessStatus(ThreeDBrightness),
+    FreezeStatus(bool),
+    HorizontalReverseStatus(bool),
+    VerticalReverseStatus(bool),
+    SerialNumberStatus(String),
+    Raw(String),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -143,6 +584,37 @@ pub enum EpsonInput {
     QuerySource,
     SetPower(Power),
     SetSource(Source),
+    QueryError,
+    QueryVolume,
+    SetVolume(u8),
+    AdjustVolume(VolumeAdjust),
+    QueryMute,
+    SetMute(bool),
+    QueryMuteColor,
+    SetMuteColor(MuteColor),
+    QueryLampHours,
+    QueryFilterHours,
+    QueryColorMode,
+    SetColorMode(ColorMode),
+    QueryPictureSetting(PictureSetting),
+    SetPictureSetting(PictureSetting, u8),
+    QueryAspect,
+    SetAspect(Aspect),
+    Key(RemoteKey),
+    QueryThreeDMode,
+    SetThreeDMode(ThreeDMode),
+    QueryThreeDFormat,
+    SetThreeDFormat(ThreeDFormat),
+    QueryThreeDBrightness,
+    SetThreeDBrightness(ThreeDBrightness),
+    QueryFreeze,
+    SetFreeze(bool),
+    QueryHorizontalReverse,
+    SetHorizontalReverse(bool),
+    QueryVerticalReverse,
+    SetVerticalReverse(bool),
+    QuerySerialNumber,
+    Raw(String),
 }
 
 #[derive(
@@ -158,6 +630,35 @@ pub enum PowerStatus {
     WirelessHdStandby = 0x07,
 }
 
+/// Reason reported by `ERR?`, typically after `PowerStatus::AbnormalityStandby`.
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ProjectorError {
+    NoError = 0x00,
+    FanError = 0x01,
+    LampFailureAtPowerOn = 0x03,
+    HighInternalTemperature = 0x04,
+    LampError = 0x06,
+    LampCoverOpen = 0x07,
+    CinemaFilterError = 0x08,
+    CapacitorDisconnected = 0x09,
+    AutoIrisError = 0x0a,
+    SubsystemError = 0x0b,
+    LowAirFlow = 0x0c,
+    AirFlowSensorError = 0x0d,
+    PowerSupplyError = 0x0e,
+    ShutterError = 0x0f,
+    PeltierCoolingError = 0x10,
+    PumpCoolingError = 0x11,
+    StaticIrisError = 0x12,
+    BallastMismatch = 0x13,
+    ExhaustShutterError = 0x14,
+    ObstacleDetected = 0x15,
+    InterfaceBoardError = 0x16,
+}
+
 #[derive(
     Serialize, Deserialize, Copy, Clone, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
 )]
@@ -173,6 +674,20 @@ pub enum Source {
     Hdmi2 = 0xa0,
 }
 
+impl Source {
+    /// Every source the projector knows about, grouped by connector type.
+    pub const ALL: [Source; 8] = [
+        Source::Input1,
+        Source::Input2DSub15,
+        Source::Input2Rgb,
+        Source::Video,
+        Source::VideoRca,
+        Source::Input3Hdmi,
+        Source::Input3DigitalRgb,
+        Source::Hdmi2,
+    ];
+}
+
 #[derive(
     Serialize, Deserialize, Clone, Copy, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
 )]
@@ -182,6 +697,138 @@ pub enum Power {
     Off,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VolumeAdjust {
+    Increase,
+    Decrease,
+}
+
+/// Screen shown while A/V mute is on (`MSEL`).
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum MuteColor {
+    Black = 0x00,
+    Blue = 0x01,
+    Logo = 0x02,
+}
+
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ColorMode {
+    Srgb = 0x01,
+    Dynamic = 0x06,
+    Natural = 0x07,
+    XvColor = 0x0b,
+    LivingRoom = 0x0c,
+    Cinema = 0x15,
+    ThreeDDynamic = 0x17,
+    ThreeDCinema = 0x18,
+    DigitalCinema = 0x22,
+}
+
+/// Numeric picture adjustments, each queried and set with its own command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PictureSetting {
+    Brightness,
+    Contrast,
+    Sharpness,
+    Tint,
+    ColorSaturation,
+}
+
+impl PictureSetting {
+    pub const ALL: [PictureSetting; 5] = [
+        PictureSetting::Brightness,
+        PictureSetting::Contrast,
+        PictureSetting::Sharpness,
+        PictureSetting::Tint,
+        PictureSetting::ColorSaturation,
+    ];
+
+    fn command(&self) -> &'static str {
+        match self {
+            PictureSetting::Brightness => "BRIGHT",
+            PictureSetting::Contrast => "CONTRAST",
+            PictureSetting::Sharpness => "SHARP",
+            PictureSetting::Tint => "TINT",
+            PictureSetting::ColorSaturation => "COLOR",
+        }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Aspect {
+    Normal = 0x00,
+    FourThree = 0x10,
+    FourThreeZoom = 0x12,
+    SixteenNine = 0x20,
+    SixteenNineUp = 0x21,
+    SixteenNineDown = 0x22,
+    Auto = 0x30,
+    Full = 0x40,
+    Zoom = 0x50,
+    Native = 0x60,
+    Wide = 0x70,
+    AnamorphicWide = 0x80,
+    HorizontalSqueeze = 0x90,
+}
+
+/// Remote control buttons that can be emulated with `KEY`.
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum RemoteKey {
+    Power = 0x01,
+    Menu = 0x03,
+    Esc = 0x05,
+    Enter = 0x16,
+    Up = 0x35,
+    Down = 0x36,
+    Left = 0x37,
+    Right = 0x38,
+    Source = 0x48,
+    VolumeUp = 0x56,
+    VolumeDown = 0x57,
+}
+
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ThreeDMode {
+    TwoD = 0x00,
+    ThreeD = 0x01,
+}
+
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ThreeDFormat {
+    Auto = 0x00,
+    SideBySide = 0x01,
+    TopAndBottom = 0x02,
+}
+
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, ToSchema, FromPrimitive, ToPrimitive, PartialEq, Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ThreeDBrightness {
+    Low = 0x00,
+    Medium = 0x01,
+    High = 0x02,
+}
+
 impl From<PowerStatus> for Power {
     fn from(value: PowerStatus) -> Self {
         match value {
@@ -217,6 +864,7 @@ mod tests {
     }
 
     #[tokio::test]
+    #[allow(clippy::unused_io_amount)]
     pub async fn test_decode() {
         let (mut epson, mut codec) = create_codec().await;
         epson.write(b":PWR=00\r:").await.unwrap();
@@ -231,6 +879,171 @@ mod tests {
         assert!(packet.is_none(), "packet: {packet:?}");
     }
 
+    #[tokio::test]
+    pub async fn test_decode_volume() {
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"VOL=128\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::VolumeStatus(128), packet);
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_mute() {
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"MUTE=ON\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::MuteStatus(true), packet);
+
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"MSEL=02\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::MuteColorStatus(MuteColor::Logo), packet);
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_lamp_hours() {
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"LAMP=1234\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::LampHours(1234), packet);
+
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"FILTER=567\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::FilterHours(567), packet);
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_error_status() {
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"ERR=04\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(
+            EpsonOutput::ErrorStatus(ProjectorError::HighInternalTemperature),
+            packet
+        );
+
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"ERR\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::Error, packet);
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_picture() {
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"CMODE=15\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::ColorModeStatus(ColorMode::Cinema), packet);
+
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"CONTRAST=140\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(
+            EpsonOutput::PictureSettingStatus(PictureSetting::Contrast, 140),
+            packet
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_aspect() {
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"ASPECT=20\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::AspectStatus(Aspect::SixteenNine), packet);
+    }
+
+    #[tokio::test]
+    pub async fn test_encode_key() {
+        let (mut epson, mut codec) = create_codec().await;
+
+        codec.send(EpsonInput::Key(RemoteKey::Menu)).await.unwrap();
+
+        let mut buf = BytesMut::with_capacity(1000);
+        epson.read_buf(&mut buf).await.unwrap();
+
+        assert_eq!("KEY 03\r\n", std::str::from_utf8(&buf).unwrap());
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_three_d() {
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"3DFORMAT=01\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(
+            EpsonOutput::ThreeDFormatStatus(ThreeDFormat::SideBySide),
+            packet
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_encode_reverse() {
+        let (mut epson, mut codec) = create_codec().await;
+
+        codec
+            .send(EpsonInput::SetVerticalReverse(true))
+            .await
+            .unwrap();
+
+        let mut buf = BytesMut::with_capacity(1000);
+        epson.read_buf(&mut buf).await.unwrap();
+
+        assert_eq!("VREVERSE ON\r\n", std::str::from_utf8(&buf).unwrap());
+    }
+
+    #[tokio::test]
+    pub async fn test_decode_serial_number() {
+        let (mut epson, mut codec) = create_codec().await;
+        epson.write_all(b"SNO=X4Y1234567L\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(
+            EpsonOutput::SerialNumberStatus("X4Y1234567L".to_string()),
+            packet
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_raw() {
+        let (mut epson, mut codec) = create_codec().await;
+
+        codec
+            .send(EpsonInput::Raw("LUMINANCE?".to_string()))
+            .await
+            .unwrap();
+        epson.write_all(b"LUMINANCE=00\r:").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::Raw("LUMINANCE=00".to_string()), packet);
+
+        let (mut epson, mut codec) = create_codec().await;
+
+        codec
+            .send(EpsonInput::Raw("LUMINANCE 01".to_string()))
+            .await
+            .unwrap();
+        epson.write_all(b":").await.unwrap();
+
+        let packet = codec.next().await.unwrap().unwrap();
+        assert_eq!(EpsonOutput::Raw("".to_string()), packet);
+
+        let result = codec
+            .send(EpsonInput::Raw("PWR?\rPWR ON".to_string()))
+            .await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     pub async fn test_encode() {
         let (mut epson, mut codec) = create_codec().await;
@@ -242,4 +1055,22 @@ mod tests {
 
         assert_eq!("PWR?\r\n", std::str::from_utf8(&buf).unwrap());
     }
+
+    #[tokio::test]
+    pub async fn test_encode_volume() {
+        let (mut epson, mut codec) = create_codec().await;
+
+        codec.send(EpsonInput::SetVolume(12)).await.unwrap();
+        codec
+            .send(EpsonInput::AdjustVolume(VolumeAdjust::Decrease))
+            .await
+            .unwrap();
+
+        let mut buf = BytesMut::with_capacity(1000);
+        while buf.len() < "VOL 12\r\nVOL DEC\r\n".len() {
+            epson.read_buf(&mut buf).await.unwrap();
+        }
+
+        assert_eq!("VOL 12\r\nVOL DEC\r\n", std::str::from_utf8(&buf).unwrap());
+    }
 }
@@ -1,58 +1,191 @@
-use std::time::Duration;
+use std::{
+    io,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use anyhow::{anyhow, Context, Result};
 use futures::SinkExt;
-use log::info;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
 use tokio::{
-    sync::RwLock,
-    time::{sleep, timeout},
+    sync::{watch, RwLock},
+    time::{error::Elapsed, sleep, timeout, Instant},
 };
 use tokio_serial::{ClearBuffer, SerialPort, SerialPortBuilderExt, SerialStream};
 use tokio_stream::StreamExt;
 use tokio_util::codec::{Decoder, Framed};
+use utoipa::ToSchema;
 
 use crate::{
-    config::Config,
-    epson_codec::{EpsonCodec, EpsonInput, EpsonOutput, Power, PowerStatus, Source},
+    config::{find_serial_port, Config, SerialPortSelector},
+    epson_codec::{
+        Aspect, ColorMode, EpsonCodec, EpsonInput, EpsonOutput, MuteColor, PictureSetting, Power,
+        PowerStatus, ProjectorError, RemoteKey, Source, ThreeDBrightness, ThreeDFormat, ThreeDMode,
+        VolumeAdjust,
+    },
+    metrics,
 };
 
+/// Latest values seen in replies from the projector, whoever asked for them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObservedState {
+    pub power_status: Option<PowerStatus>,
+    /// `None` while the projector is off since it does not report a source.
+    pub source: Option<Source>,
+    pub error: Option<ProjectorError>,
+}
+
+const MIN_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+/// Volume and picture settings are sent as 0-255 but the projector keeps as
+/// few as 20 steps and reads back the step it rounded to.
+const MAX_SETTING_STEP: u8 = 13;
+
+/// Whether `current` is the step the projector picks for `target`. Before a
+/// write it has to be the nearest step, after one any adjacent step counts
+/// since some models round down.
+fn is_setting_step(current: u8, target: u8, written: bool) -> bool {
+    let tolerance = if written {
+        MAX_SETTING_STEP - 1
+    } else {
+        MAX_SETTING_STEP / 2
+    };
+    current.abs_diff(target) <= tolerance
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting,
+}
+
+#[derive(Clone, Debug)]
+pub struct SerialHealth {
+    pub state: ConnectionState,
+    pub serial_port: String,
+    /// Error that caused the last disconnect or failed reconnect.
+    pub last_error: Option<String>,
+    /// When the projector last answered a command.
+    pub last_reply: Option<Instant>,
+}
+
+/// The serial port, dropped after an I/O error and reopened by the next
+/// command once the backoff has passed.
+struct Connection {
+    framed: Option<Framed<SerialStream, EpsonCodec>>,
+    serial_port: String,
+    /// Search for the port again on reconnect unless pinned since a USB
+    /// adapter may come back under a different name.
+    serial_port_selector: SerialPortSelector,
+    retry_at: Instant,
+    backoff: Duration,
+    health: Arc<Mutex<SerialHealth>>,
+    /// Only set once the projector answered `SNO?`, cleared on reconnect since
+    /// the port may now lead to another projector.
+    serial_number: Option<String>,
+}
+
 pub struct EpsonSerialPort {
     read_timeout: Duration,
-    port: RwLock<Framed<SerialStream, EpsonCodec>>,
+    serial_port_selector: SerialPortSelector,
+    port: RwLock<Connection>,
+    health: Arc<Mutex<SerialHealth>>,
+    observed: watch::Sender<ObservedState>,
 }
 
 impl EpsonSerialPort {
     pub async fn new(config: &Config) -> Result<Self> {
-        info!("opening serial port {} 9600 8N1", &config.serial_port);
-        let port = tokio_serial::new(&config.serial_port, 9600)
-            .data_bits(tokio_serial::DataBits::Eight)
-            .parity(tokio_serial::Parity::None)
-            .stop_bits(tokio_serial::StopBits::One)
-            .open_native_async()
-            .context(format!("failed to open serial port {}", config.serial_port))?;
-
-        let mut port = EpsonCodec::new().framed(port);
+        let health = Arc::new(Mutex::new(SerialHealth {
+            state: ConnectionState::Connected,
+            serial_port: config.serial_port.clone(),
+            last_error: None,
+            last_reply: None,
+        }));
+        let mut port = Connection {
+            framed: Some(open_port(&config.serial_port).await?),
+            serial_port: config.serial_port.clone(),
+            serial_port_selector: config.serial_port_selector.clone(),
+            retry_at: Instant::now(),
+            backoff: MIN_RECONNECT_BACKOFF,
+            health: health.clone(),
+            serial_number: None,
+        };
 
-        port.send(EpsonInput::Noop).await?;
+        match query_serial_number(&mut port, config.read_timeout).await {
+            Ok(serial_number) => info!("projector serial number {serial_number}"),
+            Err(e) => warn!("failed to read projector serial number; error = {e}"),
+        }
 
         Ok(EpsonSerialPort {
             read_timeout: config.read_timeout,
+            serial_port_selector: config.serial_port_selector.clone(),
             port: RwLock::new(port),
+            health,
+            observed: watch::Sender::new(ObservedState::default()),
         })
     }
 
+    /// Notifies whenever a reply changes the observed power status, source or
+    /// error.
+    pub fn subscribe(&self) -> watch::Receiver<ObservedState> {
+        self.observed.subscribe()
+    }
+
+    pub fn serial_port(&self) -> String {
+        self.health.lock().unwrap().serial_port.clone()
+    }
+
+    pub fn serial_port_selector(&self) -> &SerialPortSelector {
+        &self.serial_port_selector
+    }
+
+    pub fn health(&self) -> SerialHealth {
+        self.health.lock().unwrap().clone()
+    }
+
+    /// Connected and the projector answered within `max_age`.
+    pub fn is_ready(&self, max_age: Duration) -> bool {
+        let health = self.health.lock().unwrap();
+        health.state == ConnectionState::Connected
+            && health
+                .last_reply
+                .is_some_and(|last_reply| last_reply.elapsed() <= max_age)
+    }
+
+    /// Serial number of the projector, asked again until it answers `SNO?`.
+    pub async fn serial_number(&self) -> Option<String> {
+        let mut port = self.port.write().await;
+        if port.serial_number.is_none() {
+            if let Err(e) = query_serial_number(&mut port, self.read_timeout).await {
+                warn!("failed to read projector serial number; error = {e}");
+            }
+        }
+        port.serial_number.clone()
+    }
+
     pub async fn get_power_status(&self) -> Result<PowerStatus> {
         let mut port = self.port.write().await;
         self._get_power_status(&mut port).await
     }
 
-    async fn _get_power_status(
-        &self,
-        port: &mut Framed<SerialStream, EpsonCodec>,
-    ) -> Result<PowerStatus> {
+    async fn _get_power_status(&self, port: &mut Connection) -> Result<PowerStatus> {
         let resp = write_command(port, EpsonInput::QueryPower, self.read_timeout).await?;
         match resp {
-            EpsonOutput::PowerStatus(power_status) => Ok(power_status),
+            EpsonOutput::PowerStatus(power_status) => {
+                self.observed.send_if_modified(|observed| {
+                    let changed = observed.power_status.as_ref() != Some(&power_status);
+                    observed.power_status = Some(power_status.clone());
+                    if Power::from(power_status.clone()) == Power::Off && observed.source.is_some()
+                    {
+                        observed.source = None;
+                        return true;
+                    }
+                    changed
+                });
+                Ok(power_status)
+            }
             _ => Err(anyhow!("invalid response to query power; resp = {resp:?}")),
         }
     }
@@ -62,21 +195,31 @@ impl EpsonSerialPort {
         self._get_source(&mut port).await
     }
 
-    async fn _get_source(&self, port: &mut Framed<SerialStream, EpsonCodec>) -> Result<Source> {
+    async fn _get_source(&self, port: &mut Connection) -> Result<Source> {
         let resp = write_command(port, EpsonInput::QuerySource, self.read_timeout).await?;
         match resp {
-            EpsonOutput::SourceStatus(source_status) => Ok(source_status),
+            EpsonOutput::SourceStatus(source_status) => {
+                self.observed.send_if_modified(|observed| {
+                    let changed = observed.source != Some(source_status);
+                    observed.source = Some(source_status);
+                    changed
+                });
+                Ok(source_status)
+            }
             _ => Err(anyhow!("invalid response to query source; resp = {resp:?}")),
         }
     }
 
     pub async fn set_source(&self, target_source: Source) -> Result<()> {
         let mut port = self.port.write().await;
-        for _ in 0..3 {
+        for attempt in 0..3 {
             let current_source = self._get_source(&mut port).await?;
             if current_source == target_source {
                 return Ok(());
             }
+            if attempt > 0 {
+                metrics::record_set_retry("source");
+            }
             write_command(
                 &mut port,
                 EpsonInput::SetSource(target_source),
@@ -91,11 +234,14 @@ impl EpsonSerialPort {
 
     pub async fn set_power(&self, target_power: Power) -> Result<()> {
         let mut port = self.port.write().await;
-        for _ in 0..3 {
+        for attempt in 0..3 {
             let current_power: Power = self._get_power_status(&mut port).await?.into();
             if current_power == target_power {
                 return Ok(());
             }
+            if attempt > 0 {
+                metrics::record_set_retry("power");
+            }
             write_command(
                 &mut port,
                 EpsonInput::SetPower(target_power),
@@ -107,9 +253,615 @@ impl EpsonSerialPort {
 
         Err(anyhow!("failed to set power"))
     }
+
+    pub async fn get_volume(&self) -> Result<u8> {
+        let mut port = self.port.write().await;
+        self._get_volume(&mut port).await
+    }
+
+    async fn _get_volume(&self, port: &mut Connection) -> Result<u8> {
+        let resp = write_command(port, EpsonInput::QueryVolume, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::VolumeStatus(volume) => Ok(volume),
+            _ => Err(anyhow!("invalid response to query volume; resp = {resp:?}")),
+        }
+    }
+
+    pub async fn set_volume(&self, target_volume: u8) -> Result<()> {
+        let mut port = self.port.write().await;
+        let mut written = false;
+        let mut current_volume = self._get_volume(&mut port).await?;
+        for _ in 0..3 {
+            if is_setting_step(current_volume, target_volume, written) {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetVolume(target_volume),
+                self.read_timeout,
+            )
+            .await?;
+            written = true;
+            sleep(self.read_timeout).await;
+            current_volume = self._get_volume(&mut port).await?;
+        }
+
+        Err(anyhow!(
+            "failed to set volume; target = {target_volume}, current = {current_volume}"
+        ))
+    }
+
+    pub async fn adjust_volume(&self, adjust: VolumeAdjust) -> Result<()> {
+        let mut port = self.port.write().await;
+        write_command(
+            &mut port,
+            EpsonInput::AdjustVolume(adjust),
+            self.read_timeout,
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_mute(&self) -> Result<bool> {
+        let mut port = self.port.write().await;
+        self._get_mute(&mut port).await
+    }
+
+    async fn _get_mute(&self, port: &mut Connection) -> Result<bool> {
+        let resp = write_command(port, EpsonInput::QueryMute, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::MuteStatus(mute) => Ok(mute),
+            _ => Err(anyhow!("invalid response to query mute; resp = {resp:?}")),
+        }
+    }
+
+    pub async fn set_mute(&self, target_mute: bool) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_mute = self._get_mute(&mut port).await?;
+            if current_mute == target_mute {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetMute(target_mute),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set mute"))
+    }
+
+    async fn _get_mute_color(&self, port: &mut Connection) -> Result<MuteColor> {
+        let resp = write_command(port, EpsonInput::QueryMuteColor, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::MuteColorStatus(mute_color) => Ok(mute_color),
+            _ => Err(anyhow!(
+                "invalid response to query mute color; resp = {resp:?}"
+            )),
+        }
+    }
+
+    pub async fn set_mute_color(&self, target_mute_color: MuteColor) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_mute_color = self._get_mute_color(&mut port).await?;
+            if current_mute_color == target_mute_color {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetMuteColor(target_mute_color),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set mute color"))
+    }
+
+    /// Returns `None` if the projector does not report lamp hours.
+    pub async fn get_lamp_hours(&self) -> Result<Option<u32>> {
+        let mut port = self.port.write().await;
+        let resp = write_command(&mut port, EpsonInput::QueryLampHours, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::LampHours(hours) => Ok(Some(hours)),
+            EpsonOutput::Error => Ok(None),
+            _ => Err(anyhow!(
+                "invalid response to query lamp hours; resp = {resp:?}"
+            )),
+        }
+    }
+
+    /// Returns `None` if the projector does not report filter hours.
+    pub async fn get_filter_hours(&self) -> Result<Option<u32>> {
+        let mut port = self.port.write().await;
+        let resp =
+            write_command(&mut port, EpsonInput::QueryFilterHours, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::FilterHours(hours) => Ok(Some(hours)),
+            EpsonOutput::Error => Ok(None),
+            _ => Err(anyhow!(
+                "invalid response to query filter hours; resp = {resp:?}"
+            )),
+        }
+    }
+
+    pub async fn get_error(&self) -> Result<ProjectorError> {
+        let mut port = self.port.write().await;
+        let resp = write_command(&mut port, EpsonInput::QueryError, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::ErrorStatus(error) => {
+                self.observed.send_if_modified(|observed| {
+                    let changed = observed.error != Some(error);
+                    observed.error = Some(error);
+                    changed
+                });
+                Ok(error)
+            }
+            _ => Err(anyhow!("invalid response to query error; resp = {resp:?}")),
+        }
+    }
+
+    pub async fn get_color_mode(&self) -> Result<ColorMode> {
+        let mut port = self.port.write().await;
+        self._get_color_mode(&mut port).await
+    }
+
+    async fn _get_color_mode(&self, port: &mut Connection) -> Result<ColorMode> {
+        let resp = write_command(port, EpsonInput::QueryColorMode, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::ColorModeStatus(color_mode) => Ok(color_mode),
+            _ => Err(anyhow!(
+                "invalid response to query color mode; resp = {resp:?}"
+            )),
+        }
+    }
+
+    pub async fn set_color_mode(&self, target_color_mode: ColorMode) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_color_mode = self._get_color_mode(&mut port).await?;
+            if current_color_mode == target_color_mode {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetColorMode(target_color_mode),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set color mode"))
+    }
+
+    pub async fn get_picture_setting(&self, setting: PictureSetting) -> Result<u8> {
+        let mut port = self.port.write().await;
+        self._get_picture_setting(&mut port, setting).await
+    }
+
+    async fn _get_picture_setting(
+        &self,
+        port: &mut Connection,
+        setting: PictureSetting,
+    ) -> Result<u8> {
+        let resp = write_command(
+            port,
+            EpsonInput::QueryPictureSetting(setting),
+            self.read_timeout,
+        )
+        .await?;
+        match resp {
+            EpsonOutput::PictureSettingStatus(resp_setting, value) if resp_setting == setting => {
+                Ok(value)
+            }
+            _ => Err(anyhow!(
+                "invalid response to query {setting:?}; resp = {resp:?}"
+            )),
+        }
+    }
+
+    pub async fn set_picture_setting(
+        &self,
+        setting: PictureSetting,
+        target_value: u8,
+    ) -> Result<()> {
+        let mut port = self.port.write().await;
+        let mut written = false;
+        let mut current_value = self._get_picture_setting(&mut port, setting).await?;
+        for _ in 0..3 {
+            if is_setting_step(current_value, target_value, written) {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetPictureSetting(setting, target_value),
+                self.read_timeout,
+            )
+            .await?;
+            written = true;
+            sleep(self.read_timeout).await;
+            current_value = self._get_picture_setting(&mut port, setting).await?;
+        }
+
+        Err(anyhow!(
+            "failed to set {setting:?}; target = {target_value}, current = {current_value}"
+        ))
+    }
+
+    pub async fn get_aspect(&self) -> Result<Aspect> {
+        let mut port = self.port.write().await;
+        self._get_aspect(&mut port).await
+    }
+
+    async fn _get_aspect(&self, port: &mut Connection) -> Result<Aspect> {
+        let resp = write_command(port, EpsonInput::QueryAspect, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::AspectStatus(aspect) => Ok(aspect),
+            _ => Err(anyhow!("invalid response to query aspect; resp = {resp:?}")),
+        }
+    }
+
+    pub async fn set_aspect(&self, target_aspect: Aspect) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_aspect = self._get_aspect(&mut port).await?;
+            if current_aspect == target_aspect {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetAspect(target_aspect),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set aspect"))
+    }
+
+    pub async fn press_key(&self, key: RemoteKey) -> Result<()> {
+        let mut port = self.port.write().await;
+        write_command(&mut port, EpsonInput::Key(key), self.read_timeout).await?;
+        Ok(())
+    }
+
+    pub async fn get_three_d_mode(&self) -> Result<ThreeDMode> {
+        let mut port = self.port.write().await;
+        self._get_three_d_mode(&mut port).await
+    }
+
+    async fn _get_three_d_mode(&self, port: &mut Connection) -> Result<ThreeDMode> {
+        let resp = write_command(port, EpsonInput::QueryThreeDMode, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::ThreeDModeStatus(mode) => Ok(mode),
+            _ => Err(anyhow!(
+                "invalid response to query 3D mode; resp = {resp:?}"
+            )),
+        }
+    }
+
+    pub async fn set_three_d_mode(&self, target_mode: ThreeDMode) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_mode = self._get_three_d_mode(&mut port).await?;
+            if current_mode == target_mode {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetThreeDMode(target_mode),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set 3D mode"))
+    }
+
+    pub async fn get_three_d_format(&self) -> Result<ThreeDFormat> {
+        let mut port = self.port.write().await;
+        self._get_three_d_format(&mut port).await
+    }
+
+    async fn _get_three_d_format(&self, port: &mut Connection) -> Result<ThreeDFormat> {
+        let resp = write_command(port, EpsonInput::QueryThreeDFormat, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::ThreeDFormatStatus(format) => Ok(format),
+            _ => Err(anyhow!(
+                "invalid response to query 3D format; resp = {resp:?}"
+            )),
+        }
+    }
+
+    pub async fn set_three_d_format(&self, target_format: ThreeDFormat) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_format = self._get_three_d_format(&mut port).await?;
+            if current_format == target_format {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetThreeDFormat(target_format),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set 3D format"))
+    }
+
+    pub async fn get_three_d_brightness(&self) -> Result<ThreeDBrightness> {
+        let mut port = self.port.write().await;
+        self._get_three_d_brightness(&mut port).await
+    }
+
+    async fn _get_three_d_brightness(&self, port: &mut Connection) -> Result<ThreeDBrightness> {
+        let resp =
+            write_command(port, EpsonInput::QueryThreeDBrightness, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::ThreeDBrightnessStatus(brightness) => Ok(brightness),
+            _ => Err(anyhow!(
+                "invalid response to query 3D brightness; resp = {resp:?}"
+            )),
+        }
+    }
+
+    pub async fn set_three_d_brightness(&self, target_brightness: ThreeDBrightness) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_brightness = self._get_three_d_brightness(&mut port).await?;
+            if current_brightness == target_brightness {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetThreeDBrightness(target_brightness),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set 3D brightness"))
+    }
+
+    pub async fn get_freeze(&self) -> Result<bool> {
+        let mut port = self.port.write().await;
+        self._get_freeze(&mut port).await
+    }
+
+    async fn _get_freeze(&self, port: &mut Connection) -> Result<bool> {
+        let resp = write_command(port, EpsonInput::QueryFreeze, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::FreezeStatus(freeze) => Ok(freeze),
+            _ => Err(anyhow!("invalid response to query freeze; resp = {resp:?}")),
+        }
+    }
+
+    pub async fn set_freeze(&self, target_freeze: bool) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_freeze = self._get_freeze(&mut port).await?;
+            if current_freeze == target_freeze {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetFreeze(target_freeze),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set freeze"))
+    }
+
+    pub async fn get_horizontal_reverse(&self) -> Result<bool> {
+        let mut port = self.port.write().await;
+        self._get_horizontal_reverse(&mut port).await
+    }
+
+    async fn _get_horizontal_reverse(&self, port: &mut Connection) -> Result<bool> {
+        let resp =
+            write_command(port, EpsonInput::QueryHorizontalReverse, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::HorizontalReverseStatus(reverse) => Ok(reverse),
+            _ => Err(anyhow!(
+                "invalid response to query horizontal reverse; resp = {resp:?}"
+            )),
+        }
+    }
+
+    pub async fn set_horizontal_reverse(&self, target_reverse: bool) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_reverse = self._get_horizontal_reverse(&mut port).await?;
+            if current_reverse == target_reverse {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetHorizontalReverse(target_reverse),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set horizontal reverse"))
+    }
+
+    pub async fn get_vertical_reverse(&self) -> Result<bool> {
+        let mut port = self.port.write().await;
+        self._get_vertical_reverse(&mut port).await
+    }
+
+    async fn _get_vertical_reverse(&self, port: &mut Connection) -> Result<bool> {
+        let resp = write_command(port, EpsonInput::QueryVerticalReverse, self.read_timeout).await?;
+        match resp {
+            EpsonOutput::VerticalReverseStatus(reverse) => Ok(reverse),
+            _ => Err(anyhow!(
+                "invalid response to query vertical reverse; resp = {resp:?}"
+            )),
+        }
+    }
+
+    pub async fn set_vertical_reverse(&self, target_reverse: bool) -> Result<()> {
+        let mut port = self.port.write().await;
+        for _ in 0..3 {
+            let current_reverse = self._get_vertical_reverse(&mut port).await?;
+            if current_reverse == target_reverse {
+                return Ok(());
+            }
+            write_command(
+                &mut port,
+                EpsonInput::SetVerticalReverse(target_reverse),
+                self.read_timeout,
+            )
+            .await?;
+            sleep(self.read_timeout).await;
+        }
+
+        Err(anyhow!("failed to set vertical reverse"))
+    }
+
+    /// Sends an arbitrary ESC/VP21 command and returns the reply line, an empty
+    /// string if the projector only answered with the prompt, or `ERR`.
+    pub async fn send_raw(&self, cmd: &str) -> Result<String> {
+        let mut port = self.port.write().await;
+        let resp = write_command(
+            &mut port,
+            EpsonInput::Raw(cmd.to_string()),
+            self.read_timeout,
+        )
+        .await?;
+        match resp {
+            EpsonOutput::Raw(line) => Ok(line),
+            EpsonOutput::Error => Ok("ERR".to_string()),
+            _ => Err(anyhow!("invalid response to raw command; resp = {resp:?}")),
+        }
+    }
 }
 
+impl Connection {
+    async fn get(&mut self) -> Result<&mut Framed<SerialStream, EpsonCodec>> {
+        if self.framed.is_none() {
+            let framed = self.reconnect().await?;
+            self.framed = Some(framed);
+        }
+        self.framed
+            .as_mut()
+            .ok_or_else(|| anyhow!("serial port not connected"))
+    }
+
+    async fn reconnect(&mut self) -> Result<Framed<SerialStream, EpsonCodec>> {
+        if Instant::now() < self.retry_at {
+            return Err(anyhow!(
+                "serial port {} disconnected, reconnecting",
+                self.serial_port
+            ));
+        }
+
+        let result = async {
+            if !self.serial_port_selector.is_pinned() {
+                // udev enumeration blocks
+                let selector = self.serial_port_selector.clone();
+                self.serial_port =
+                    tokio::task::spawn_blocking(move || find_serial_port(&selector)).await??;
+            }
+            open_port(&self.serial_port).await
+        }
+        .await;
+
+        let mut health = self.health.lock().unwrap();
+        health.serial_port = self.serial_port.clone();
+        match result {
+            Ok(framed) => {
+                info!("reconnected serial port {}", self.serial_port);
+                self.backoff = MIN_RECONNECT_BACKOFF;
+                self.serial_number = None;
+                health.state = ConnectionState::Connected;
+                Ok(framed)
+            }
+            Err(e) => {
+                warn!(
+                    "failed to reconnect serial port, retrying in {:?}; error = {e}",
+                    self.backoff
+                );
+                self.retry_at = Instant::now() + self.backoff;
+                self.backoff = (self.backoff * 2).min(MAX_RECONNECT_BACKOFF);
+                health.last_error = Some(format!("{e}"));
+                Err(e)
+            }
+        }
+    }
+
+    fn disconnect(&mut self, e: &anyhow::Error) {
+        warn!(
+            "serial port {} failed, reconnecting; error = {e}",
+            self.serial_port
+        );
+        self.framed = None;
+        self.retry_at = Instant::now();
+        let mut health = self.health.lock().unwrap();
+        health.state = ConnectionState::Reconnecting;
+        health.last_error = Some(format!("{e}"));
+    }
+}
+
+async fn open_port(serial_port: &str) -> Result<Framed<SerialStream, EpsonCodec>> {
+    info!("opening serial port {serial_port} 9600 8N1");
+    let port = tokio_serial::new(serial_port, 9600)
+        .data_bits(tokio_serial::DataBits::Eight)
+        .parity(tokio_serial::Parity::None)
+        .stop_bits(tokio_serial::StopBits::One)
+        .open_native_async()
+        .context(format!("failed to open serial port {serial_port}"))?;
+
+    let mut port = EpsonCodec::new().framed(port);
+
+    port.send(EpsonInput::Noop).await?;
+    Ok(port)
+}
+
+/// Writes the command and reads the reply, dropping the connection if the
+/// serial port itself failed.
 async fn write_command(
+    port: &mut Connection,
+    cmd: EpsonInput,
+    read_timeout: Duration,
+) -> Result<EpsonOutput> {
+    let command = metrics::command_label(&cmd);
+    let start = Instant::now();
+    let ret = exchange(port.get().await?, cmd, read_timeout).await;
+    let outcome = match &ret {
+        Ok(EpsonOutput::Error) => "err",
+        Ok(EpsonOutput::InvalidLine(_)) => "invalid_line",
+        Ok(_) => "ok",
+        Err(e) if e.is::<Elapsed>() => "timeout",
+        Err(_) => "failed",
+    };
+    metrics::record_command(&command, outcome, start.elapsed());
+    match &ret {
+        Ok(_) => port.health.lock().unwrap().last_reply = Some(Instant::now()),
+        Err(e) if is_io_error(e) => port.disconnect(e),
+        Err(_) => {}
+    }
+    ret
+}
+
+async fn exchange(
     port: &mut Framed<SerialStream, EpsonCodec>,
     cmd: EpsonInput,
     read_timeout: Duration,
@@ -118,13 +870,218 @@ async fn write_command(
     port.send(cmd).await?;
     let ret = timeout(read_timeout, port.next()).await?;
     match ret {
-        Some(ret) => ret,
+        Some(Ok(ret)) => Ok(ret),
+        Some(Err(e)) => {
+            // after a decode error `Framed` yields `None` once, take it now so
+            // the next command reads its own reply
+            if !is_io_error(&e) {
+                let _ = timeout(Duration::ZERO, port.next()).await;
+            }
+            Err(e)
+        }
         None => Err(anyhow!("failed to read response, nothing returned")),
     }
 }
 
+/// Timeouts and unexpected replies are the projector's doing, only errors from
+/// the serial port itself mean it is gone.
+fn is_io_error(e: &anyhow::Error) -> bool {
+    e.chain()
+        .any(|cause| cause.is::<io::Error>() || cause.is::<tokio_serial::Error>())
+}
+
+async fn query_serial_number(port: &mut Connection, read_timeout: Duration) -> Result<String> {
+    let resp = write_command(port, EpsonInput::QuerySerialNumber, read_timeout).await?;
+    match resp {
+        EpsonOutput::SerialNumberStatus(serial_number) => {
+            port.serial_number = Some(serial_number.clone());
+            Ok(serial_number)
+        }
+        _ => Err(anyhow!(
+            "invalid response to query serial number; resp = {resp:?}"
+        )),
+    }
+}
+
 async fn clear_port(port: &mut Framed<SerialStream, EpsonCodec>) -> Result<()> {
     port.read_buffer_mut().clear();
     port.get_ref().clear(ClearBuffer::All)?;
     Ok(())
 }
+
+#[cfg(test)]
+pub mod testing {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    /// The bridge on one end of a pty pair, the other end plays the projector.
+    pub fn create_epson() -> (SerialStream, EpsonSerialPort) {
+        let (bridge, projector) = SerialStream::pair().unwrap();
+        let health = Arc::new(Mutex::new(SerialHealth {
+            state: ConnectionState::Connected,
+            serial_port: "pty".to_string(),
+            last_error: None,
+            last_reply: None,
+        }));
+        let port = Connection {
+            framed: Some(EpsonCodec::new().framed(bridge)),
+            serial_port: "pty".to_string(),
+            serial_port_selector: SerialPortSelector::default(),
+            retry_at: Instant::now(),
+            backoff: MIN_RECONNECT_BACKOFF,
+            health: health.clone(),
+            serial_number: None,
+        };
+        let epson = EpsonSerialPort {
+            read_timeout: Duration::from_secs(1),
+            serial_port_selector: SerialPortSelector::default(),
+            port: RwLock::new(port),
+            health,
+            observed: watch::Sender::new(ObservedState::default()),
+        };
+        (projector, epson)
+    }
+
+    async fn read_command(projector: &mut SerialStream) -> String {
+        let mut line = vec![];
+        while !line.ends_with(b"\r\n") {
+            line.push(projector.read_u8().await.unwrap());
+        }
+        String::from_utf8_lossy(&line).trim().to_string()
+    }
+
+    /// Answers each command with the next reply.
+    pub async fn reply(projector: &mut SerialStream, replies: &[&str]) {
+        for reply in replies {
+            read_command(projector).await;
+            projector.write_all(reply.as_bytes()).await.unwrap();
+        }
+    }
+
+    /// Plays the projector for the rest of the test, answering every command
+    /// with `answer`. Returns the commands received so far.
+    pub fn spawn_projector<F>(mut projector: SerialStream, answer: F) -> Arc<Mutex<Vec<String>>>
+    where
+        F: Fn(&str) -> String + Send + 'static,
+    {
+        let commands = Arc::new(Mutex::new(vec![]));
+        let received = commands.clone();
+        tokio::spawn(async move {
+            loop {
+                let command = read_command(&mut projector).await;
+                let reply = answer(&command);
+                received.lock().unwrap().push(command);
+                projector.write_all(reply.as_bytes()).await.unwrap();
+            }
+        });
+        commands
+    }
+
+    /// Answers like a projector that is on and showing HDMI.
+    pub fn lamp_on(command: &str) -> String {
+        match command {
+            "PWR?" => "PWR=01\r:",
+            "SOURCE?" => "SOURCE=30\r:",
+            "MUTE?" => "MUTE=OFF\r:",
+            "ASPECT?" => "ASPECT=00\r:",
+            "LAMP?" => "LAMP=1234\r:",
+            "ERR?" => "ERR=00\r:",
+            _ => "ERR\r:",
+        }
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{testing::*, *};
+
+    #[tokio::test]
+    pub async fn test_decode_error_keeps_connection() {
+        let (mut projector, epson) = create_epson();
+        let projector = tokio::spawn(async move {
+            reply(&mut projector, &["ASPECT=99\r:", "PWR=01\r:"]).await;
+            projector
+        });
+
+        assert!(epson.get_aspect().await.is_err());
+        assert_eq!(PowerStatus::LampOn, epson.get_power_status().await.unwrap());
+
+        let health = epson.health();
+        assert_eq!(ConnectionState::Connected, health.state);
+        assert_eq!(None, health.last_error);
+        projector.await.unwrap();
+    }
+
+    #[tokio::test]
+    pub async fn test_is_ready_after_reply() {
+        let (mut projector, epson) = create_epson();
+        assert!(!epson.is_ready(Duration::from_secs(1)));
+
+        let projector = tokio::spawn(async move {
+            reply(&mut projector, &["PWR=00\r:"]).await;
+            projector
+        });
+        epson.get_power_status().await.unwrap();
+        assert!(epson.is_ready(Duration::from_secs(1)));
+        assert!(!epson.is_ready(Duration::ZERO));
+        projector.await.unwrap();
+    }
+
+    #[test]
+    pub fn test_is_setting_step() {
+        assert!(is_setting_step(128, 128, false));
+        assert!(is_setting_step(127, 130, false));
+        assert!(!is_setting_step(115, 130, false));
+        assert!(is_setting_step(128, 140, true));
+        assert!(!is_setting_step(115, 130, true));
+    }
+
+    #[tokio::test]
+    pub async fn test_set_volume_already_at_nearest_step() {
+        let (mut projector, epson) = create_epson();
+        let projector = tokio::spawn(async move {
+            reply(&mut projector, &["VOL=127\r:"]).await;
+            projector
+        });
+
+        epson.set_volume(130).await.unwrap();
+        projector.await.unwrap();
+    }
+
+    #[tokio::test]
+    pub async fn test_set_picture_setting_already_at_nearest_step() {
+        let (mut projector, epson) = create_epson();
+        let projector = tokio::spawn(async move {
+            reply(&mut projector, &["BRIGHT=132\r:"]).await;
+            projector
+        });
+
+        epson
+            .set_picture_setting(PictureSetting::Brightness, 128)
+            .await
+            .unwrap();
+        projector.await.unwrap();
+    }
+
+    #[tokio::test]
+    pub async fn test_serial_number_cached_once_answered() {
+        let (projector, epson) = create_epson();
+        let answered = Arc::new(Mutex::new(false));
+        let projector_answered = answered.clone();
+        let commands = spawn_projector(projector, move |_| {
+            if *projector_answered.lock().unwrap() {
+                "SNO=X4KE1234\r:".to_string()
+            } else {
+                "ERR\r:".to_string()
+            }
+        });
+
+        assert_eq!(None, epson.serial_number().await);
+        *answered.lock().unwrap() = true;
+        assert_eq!(Some("X4KE1234".to_string()), epson.serial_number().await);
+        assert_eq!(Some("X4KE1234".to_string()), epson.serial_number().await);
+        assert_eq!(vec!["SNO?", "SNO?"], *commands.lock().unwrap());
+    }
+}
@@ -0,0 +1,275 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::{anyhow, Context, Result};
+use bytes::{Buf, BytesMut};
+use log::{debug, error, info, warn};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream, UdpSocket},
+};
+
+use crate::{config::Config, state::EpsonState};
+
+const MAGIC: &[u8; 10] = b"ESC/VP.net";
+const PROTOCOL_VERSION: u8 = 0x10;
+const HEADER_LEN: usize = 16;
+const SUB_HEADER_LEN: usize = 18;
+const SUB_HEADER_PASSWORD: u8 = 0x01;
+/// Longest command line accepted, ESC/VP21 commands are far shorter.
+const MAX_LINE_LEN: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PacketType {
+    Null = 0x00,
+    Hello = 0x01,
+    Password = 0x02,
+    Connect = 0x03,
+}
+
+impl PacketType {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x00 => Some(PacketType::Null),
+            0x01 => Some(PacketType::Hello),
+            0x02 => Some(PacketType::Password),
+            0x03 => Some(PacketType::Connect),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Ok = 0x20,
+    BadRequest = 0x40,
+    /// Missing or wrong password.
+    Unauthorized = 0x41,
+    UnsupportedVersion = 0x55,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Header {
+    version: u8,
+    packet_type: u8,
+    sub_header_count: u8,
+}
+
+impl Header {
+    fn parse(buf: &[u8; HEADER_LEN]) -> Result<Header> {
+        if &buf[..MAGIC.len()] != MAGIC {
+            return Err(anyhow!(
+                "invalid ESC/VP.net magic {:?}",
+                &buf[..MAGIC.len()]
+            ));
+        }
+        Ok(Header {
+            version: buf[10],
+            packet_type: buf[11],
+            sub_header_count: buf[15],
+        })
+    }
+}
+
+fn encode_response(packet_type: u8, status: Status) -> [u8; HEADER_LEN] {
+    let mut buf = [0u8; HEADER_LEN];
+    buf[..MAGIC.len()].copy_from_slice(MAGIC);
+    buf[10] = PROTOCOL_VERSION;
+    buf[11] = packet_type;
+    buf[14] = status as u8;
+    buf
+}
+
+/// Returns the password carried in the sub headers of a `CONNECT` request.
+fn find_password(sub_headers: &[u8]) -> Option<String> {
+    sub_headers
+        .chunks_exact(SUB_HEADER_LEN)
+        .find(|sub_header| sub_header[0] == SUB_HEADER_PASSWORD)
+        .map(|sub_header| {
+            let info = &sub_header[2..];
+            let end = info.iter().position(|b| *b == 0).unwrap_or(info.len());
+            String::from_utf8_lossy(&info[..end]).to_string()
+        })
+}
+
+fn check_request(header: &Header) -> Status {
+    if header.version != PROTOCOL_VERSION {
+        Status::UnsupportedVersion
+    } else if PacketType::from_u8(header.packet_type).is_none() {
+        Status::BadRequest
+    } else {
+        Status::Ok
+    }
+}
+
+/// Makes the bridge look like a networked Epson projector to tools that speak
+/// ESC/VP.net (Epson's own software, Crestron, Extron, ...). After the
+/// `CONNECT` handshake every ESC/VP21 line is forwarded to the serial port, so
+/// it only runs when raw commands are enabled.
+pub async fn escvpnet_start_server(config: &Config, state: Arc<EpsonState>) -> Result<()> {
+    let Some(port) = config.escvpnet_port else {
+        return Ok(());
+    };
+    if !config.raw_commands_enabled {
+        warn!("ESCVPNET_PORT is set but RAW_COMMANDS_ENABLED is not, ESC/VP.net disabled");
+        return Ok(());
+    }
+
+    let socket_address: SocketAddr = format!("0.0.0.0:{port}")
+        .parse()
+        .context("parse socket addr")?;
+    let listener = TcpListener::bind(socket_address)
+        .await
+        .context(format!("binding to {socket_address}"))?;
+    let discovery = UdpSocket::bind(socket_address)
+        .await
+        .context(format!("binding udp to {socket_address}"))?;
+    tokio::spawn(async move {
+        if let Err(e) = run_discovery(discovery).await {
+            error!("ESC/VP.net discovery stopped; error = {e}");
+        }
+    });
+
+    info!("listening ESC/VP.net on port {port}");
+    loop {
+        let (stream, addr) = listener.accept().await.context("accepting")?;
+        let state = state.clone();
+        let password = config.escvpnet_password.clone();
+        tokio::spawn(async move {
+            debug!("ESC/VP.net client connected {addr}");
+            if let Err(e) = handle_client(stream, state, password).await {
+                warn!("ESC/VP.net client {addr} failed; error = {e}");
+            }
+            debug!("ESC/VP.net client disconnected {addr}");
+        });
+    }
+}
+
+/// Answers `HELLO` broadcasts so the bridge shows up in projector searches.
+async fn run_discovery(socket: UdpSocket) -> Result<()> {
+    let mut buf = [0u8; 1024];
+    loop {
+        let (len, addr) = socket.recv_from(&mut buf).await?;
+        let Ok(header) = <&[u8; HEADER_LEN]>::try_from(&buf[..len.min(HEADER_LEN)]) else {
+            continue;
+        };
+        match Header::parse(header) {
+            Ok(header) if header.packet_type == PacketType::Hello as u8 => {
+                debug!("ESC/VP.net discovery from {addr}");
+                let resp = encode_response(header.packet_type, check_request(&header));
+                socket.send_to(&resp, addr).await?;
+            }
+            Ok(_) => {}
+            Err(e) => debug!("ignoring ESC/VP.net datagram from {addr}; error = {e}"),
+        }
+    }
+}
+
+async fn handle_client(
+    mut stream: TcpStream,
+    state: Arc<EpsonState>,
+    password: Option<String>,
+) -> Result<()> {
+    loop {
+        let mut buf = [0u8; HEADER_LEN];
+        stream.read_exact(&mut buf).await?;
+        let header = Header::parse(&buf)?;
+
+        let mut sub_headers = vec![0u8; header.sub_header_count as usize * SUB_HEADER_LEN];
+        stream.read_exact(&mut sub_headers).await?;
+
+        let mut status = check_request(&header);
+        if status == Status::Ok && header.packet_type == PacketType::Connect as u8 {
+            if let Some(password) = &password {
+                if find_password(&sub_headers).as_ref() != Some(password) {
+                    status = Status::Unauthorized;
+                }
+            }
+        }
+
+        stream
+            .write_all(&encode_response(header.packet_type, status))
+            .await?;
+
+        if header.packet_type == PacketType::Connect as u8 && status == Status::Ok {
+            return forward_commands(stream, state).await;
+        }
+    }
+}
+
+async fn forward_commands(mut stream: TcpStream, state: Arc<EpsonState>) -> Result<()> {
+    let mut buf = BytesMut::with_capacity(1024);
+    loop {
+        while let Some(offset) = buf.iter().position(|b| *b == b'\r') {
+            let line = buf.split_to(offset);
+            buf.advance(b"\r".len());
+            let cmd = String::from_utf8_lossy(&line).trim().to_string();
+
+            let resp = if cmd.is_empty() {
+                ":".to_string()
+            } else {
+                match state.epson.send_raw(&cmd).await {
+                    Ok(reply) if reply.is_empty() => ":".to_string(),
+                    Ok(reply) => format!("{reply}\r:"),
+                    Err(e) => {
+                        warn!("ESC/VP.net command {cmd} failed; error = {e}");
+                        "ERR\r:".to_string()
+                    }
+                }
+            };
+            stream.write_all(resp.as_bytes()).await?;
+        }
+
+        if buf.len() > MAX_LINE_LEN {
+            return Err(anyhow!("command longer than {MAX_LINE_LEN} bytes"));
+        }
+        if stream.read_buf(&mut buf).await? == 0 {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connect_request(password: Option<&str>) -> Vec<u8> {
+        let mut buf = Vec::from(*MAGIC);
+        buf.extend_from_slice(&[PROTOCOL_VERSION, PacketType::Connect as u8, 0, 0, 0]);
+        match password {
+            Some(password) => {
+                buf.push(1);
+                let mut sub_header = [0u8; SUB_HEADER_LEN];
+                sub_header[0] = SUB_HEADER_PASSWORD;
+                sub_header[2..2 + password.len()].copy_from_slice(password.as_bytes());
+                buf.extend_from_slice(&sub_header);
+            }
+            None => buf.push(0),
+        }
+        buf
+    }
+
+    #[test]
+    pub fn test_parse_connect() {
+        let buf = connect_request(Some("admin"));
+        let header = Header::parse(buf[..HEADER_LEN].try_into().unwrap()).unwrap();
+        assert_eq!(
+            Header {
+                version: PROTOCOL_VERSION,
+                packet_type: PacketType::Connect as u8,
+                sub_header_count: 1,
+            },
+            header
+        );
+        assert_eq!(Status::Ok, check_request(&header));
+        assert_eq!(Some("admin".to_string()), find_password(&buf[HEADER_LEN..]));
+
+        let buf = connect_request(None);
+        assert_eq!(None, find_password(&buf[HEADER_LEN..]));
+    }
+
+    #[test]
+    pub fn test_encode_response() {
+        let resp = encode_response(PacketType::Connect as u8, Status::Unauthorized);
+        assert_eq!(b"ESC/VP.net\x10\x03\x00\x00\x41\x00", &resp);
+    }
+}
@@ -1,19 +1,32 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use anyhow::{Context, Result};
 use axum::{
+    extract::State,
+    http::StatusCode,
+    middleware,
     response::{Html, IntoResponse},
     routing::{get, post},
 };
 use log::info;
-use tokio::net::TcpListener;
+use tokio::{net::TcpListener, sync::oneshot};
 use utoipa::OpenApi;
 use utoipa_redoc::{Redoc, Servable as RedocServable};
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
     config::Config,
-    routes::{self, get_status::get_status, post_power::post_power, post_source::post_source},
+    metrics::{get_metrics, track_http},
+    routes::{
+        self, get_errors::get_errors, get_events::get_events, get_freeze::get_freeze,
+        get_health::get_health, get_info::get_info, get_lamp::get_lamp,
+        get_orientation::get_orientation, get_picture::get_picture,
+        get_serial_ports::get_serial_ports, get_status::get_status, get_three_d::get_three_d,
+        get_volume::get_volume, patch_picture::patch_picture, post_aspect::post_aspect,
+        post_freeze::post_freeze, post_key::post_key, post_mute::post_mute,
+        post_orientation::post_orientation, post_power::post_power, post_raw::post_raw,
+        post_source::post_source, post_three_d::post_three_d, post_volume::post_volume,
+    },
     state::EpsonState,
 };
 
@@ -23,7 +36,27 @@ use crate::{
     paths(
         routes::get_status::get_status,
         routes::post_source::post_source,
-        routes::post_power::post_power
+        routes::post_power::post_power,
+        routes::get_volume::get_volume,
+        routes::post_volume::post_volume,
+        routes::post_mute::post_mute,
+        routes::get_lamp::get_lamp,
+        routes::get_errors::get_errors,
+        routes::get_picture::get_picture,
+        routes::patch_picture::patch_picture,
+        routes::post_aspect::post_aspect,
+        routes::post_key::post_key,
+        routes::get_three_d::get_three_d,
+        routes::post_three_d::post_three_d,
+        routes::get_freeze::get_freeze,
+        routes::post_freeze::post_freeze,
+        routes::get_orientation::get_orientation,
+        routes::post_orientation::post_orientation,
+        routes::get_info::get_info,
+        routes::post_raw::post_raw,
+        routes::get_events::get_events,
+        routes::get_health::get_health,
+        routes::get_serial_ports::get_serial_ports
     ),
     components(schemas(
         routes::ErrorResponse,
@@ -31,27 +64,99 @@ use crate::{
         routes::get_status::GetStatusResponse,
         routes::post_source::PostSourceRequest,
         routes::post_power::PostPowerRequest,
+        routes::get_volume::GetVolumeResponse,
+        routes::post_volume::PostVolumeRequest,
+        routes::post_mute::PostMuteRequest,
+        routes::get_lamp::GetLampResponse,
+        routes::get_errors::GetErrorsResponse,
+        routes::get_picture::GetPictureResponse,
+        routes::patch_picture::PatchPictureRequest,
+        routes::post_aspect::PostAspectRequest,
+        routes::post_key::PostKeyRequest,
+        routes::get_three_d::GetThreeDResponse,
+        routes::post_three_d::PostThreeDRequest,
+        routes::get_freeze::GetFreezeResponse,
+        routes::post_freeze::PostFreezeRequest,
+        routes::get_orientation::GetOrientationResponse,
+        routes::post_orientation::PostOrientationRequest,
+        routes::get_info::GetInfoResponse,
+        routes::post_raw::PostRawRequest,
+        routes::post_raw::PostRawResponse,
+        routes::get_events::StateEvent,
+        routes::get_health::GetHealthResponse,
+        routes::get_serial_ports::GetSerialPortsResponse,
+        routes::get_serial_ports::SerialPortResponse,
         super::epson_codec::Power,
         super::epson_codec::PowerStatus,
+        super::epson_codec::ProjectorError,
         super::epson_codec::Source,
+        super::epson_codec::VolumeAdjust,
+        super::epson_codec::MuteColor,
+        super::epson_codec::ColorMode,
+        super::epson_codec::Aspect,
+        super::epson_codec::RemoteKey,
+        super::epson_codec::ThreeDMode,
+        super::epson_codec::ThreeDFormat,
+        super::epson_codec::ThreeDBrightness,
+        super::epson_serial_port::ConnectionState,
+        super::config::SerialPortSelection,
     ))
 )]
 struct ApiDoc;
 
-pub async fn http_start_server(config: &Config, state: Arc<EpsonState>) -> Result<()> {
+/// `listening` is sent once the port is bound.
+pub async fn http_start_server(
+    config: &Config,
+    state: Arc<EpsonState>,
+    listening: oneshot::Sender<()>,
+) -> Result<()> {
     let socket_address: SocketAddr = format!("0.0.0.0:{}", config.http_port)
         .parse()
         .context("parse socket addr")?;
     let listener = TcpListener::bind(socket_address)
         .await
         .context(format!("binding to {socket_address}"))?;
+    let _ = listening.send(());
 
     let app = axum::Router::new()
         .route("/api/v1/status", get(get_status))
         .route("/api/v1/source", post(post_source))
-        .route("/api/v1/power", post(post_power));
+        .route("/api/v1/power", post(post_power))
+        .route("/api/v1/volume", get(get_volume).post(post_volume))
+        .route("/api/v1/mute", post(post_mute))
+        .route("/api/v1/lamp", get(get_lamp))
+        .route("/api/v1/errors", get(get_errors))
+        .route("/api/v1/picture", get(get_picture).patch(patch_picture))
+        .route("/api/v1/aspect", post(post_aspect))
+        .route("/api/v1/key", post(post_key))
+        .route("/api/v1/3d", get(get_three_d).post(post_three_d))
+        .route("/api/v1/freeze", get(get_freeze).post(post_freeze))
+        .route(
+            "/api/v1/orientation",
+            get(get_orientation).post(post_orientation),
+        )
+        .route("/api/v1/info", get(get_info))
+        .route("/api/v1/events", get(get_events))
+        .route("/api/v1/health", get(get_health))
+        .route("/api/v1/serial/ports", get(get_serial_ports));
 
+    let app = if config.raw_commands_enabled {
+        app.route("/api/v1/raw", post(post_raw))
+    } else {
+        app
+    };
+
+    let ready_max_age = config.ready_max_age();
     let app = app
+        .route("/metrics", get(get_metrics))
+        .route("/healthz", get(handle_get_healthz))
+        .route(
+            "/readyz",
+            get(move |State(state): State<Arc<EpsonState>>| {
+                handle_get_readyz(state, ready_max_age)
+            }),
+        )
+        .route_layer(middleware::from_fn(track_http))
         .route("/docs", get(handle_get_docs))
         .merge(SwaggerUi::new("/docs/swagger-ui").url("/docs/openapi.json", ApiDoc::openapi()))
         .merge(Redoc::with_url("/docs/redoc", ApiDoc::openapi()))
@@ -63,6 +168,18 @@ pub async fn http_start_server(config: &Config, state: Arc<EpsonState>) -> Resul
         .context("serving")
 }
 
+async fn handle_get_healthz() -> impl IntoResponse {
+    "ok"
+}
+
+async fn handle_get_readyz(state: Arc<EpsonState>, max_age: Duration) -> impl IntoResponse {
+    if state.epson.is_ready(max_age) {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "not ready")
+    }
+}
+
 async fn handle_get_docs() -> impl IntoResponse {
     Html(
         r#"<html>
@@ -79,3 +196,31 @@ async fn handle_get_docs() -> impl IntoResponse {
     </html>"#,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::epson_serial_port::testing::{create_epson, reply};
+
+    use super::*;
+
+    #[tokio::test]
+    pub async fn test_readyz() {
+        let (mut projector, epson) = create_epson();
+        let state = Arc::new(EpsonState::new(epson));
+        let max_age = Duration::from_secs(1);
+        let response = handle_get_readyz(state.clone(), max_age).await;
+        assert_eq!(
+            StatusCode::SERVICE_UNAVAILABLE,
+            response.into_response().status()
+        );
+
+        let projector = tokio::spawn(async move {
+            reply(&mut projector, &["PWR=00\r:"]).await;
+            projector
+        });
+        state.epson.get_power_status().await.unwrap();
+        let response = handle_get_readyz(state.clone(), max_age).await;
+        assert_eq!(StatusCode::OK, response.into_response().status());
+        projector.await.unwrap();
+    }
+}
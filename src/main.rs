@@ -1,29 +1,62 @@
 use std::sync::Arc;
 
 use anyhow::Result;
+use clap::Parser;
+use cli::{run_command, Cli, Command, ConfigArgs};
 use config::Config;
 use epson_serial_port::EpsonSerialPort;
+use escvpnet::escvpnet_start_server;
 use http::http_start_server;
 use log::info;
+use monitor::monitor_start;
+use mqtt::mqtt_start_client;
+use pjlink::pjlink_start_server;
 use state::EpsonState;
+use systemd::systemd_notify_start;
+use tokio::sync::oneshot;
 
+mod cli;
+mod client;
 mod config;
 mod epson_codec;
 mod epson_serial_port;
+mod escvpnet;
 mod http;
 mod logger;
+mod metrics;
+mod monitor;
+mod mqtt;
+mod pjlink;
 mod routes;
 mod state;
+mod systemd;
+mod util;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let config = Config::new()?;
+    let mut cli = Cli::parse();
+    match cli.command.take().unwrap_or(Command::Serve) {
+        Command::Serve => serve(&cli.config).await,
+        command => run_command(&cli, command).await,
+    }
+}
+
+async fn serve(args: &ConfigArgs) -> Result<()> {
+    let config = Config::new(args, "info")?;
     info!("starting epson-rs232-projector-network-bridge");
 
     let epson = EpsonSerialPort::new(&config).await?;
-    let state = Arc::new(EpsonState { epson });
+    let state = Arc::new(EpsonState::new(epson));
+    let (listening_tx, listening_rx) = oneshot::channel();
 
-    http_start_server(&config, state).await?;
+    tokio::try_join!(
+        http_start_server(&config, state.clone(), listening_tx),
+        escvpnet_start_server(&config, state.clone()),
+        pjlink_start_server(&config, state.clone()),
+        mqtt_start_client(&config, state.clone()),
+        monitor_start(&config, state.clone()),
+        systemd_notify_start(&config, state, listening_rx),
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,177 @@
+use std::{
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use log::error;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
+    Encoder, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, TextEncoder,
+};
+
+use crate::{
+    epson_codec::{EpsonInput, Source},
+    state::StatusSnapshot,
+    util::serde_name,
+};
+
+static COMMANDS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "epson_commands_total",
+        "ESC/VP21 commands sent to the projector",
+        &["command", "outcome"]
+    )
+    .unwrap()
+});
+
+static COMMAND_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "epson_command_duration_seconds",
+        "Time from writing an ESC/VP21 command to its reply",
+        &["command"]
+    )
+    .unwrap()
+});
+
+static SET_RETRIES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "epson_set_retries_total",
+        "Set commands sent again because the projector did not apply the first one",
+        &["setting"]
+    )
+    .unwrap()
+});
+
+static HTTP_REQUESTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "http_requests_total",
+        "HTTP requests handled",
+        &["method", "route", "status"]
+    )
+    .unwrap()
+});
+
+static HTTP_REQUEST_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "http_request_duration_seconds",
+        "Time to handle HTTP requests",
+        &["method", "route"]
+    )
+    .unwrap()
+});
+
+static POWER_STATUS: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register_int_gauge_vec!(
+        "epson_power_status",
+        "1 for the current power status of the projector",
+        &["status"]
+    )
+    .unwrap()
+});
+
+static SOURCE: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register_int_gauge_vec!(
+        "epson_source",
+        "1 for the current source of the projector",
+        &["source"]
+    )
+    .unwrap()
+});
+
+static LAMP_HOURS: LazyLock<IntGauge> = LazyLock::new(|| {
+    register_int_gauge!(
+        "epson_lamp_hours",
+        "Lamp-on hours reported by the projector"
+    )
+    .unwrap()
+});
+
+pub fn record_command(command: &str, outcome: &str, duration: Duration) {
+    COMMANDS.with_label_values(&[command, outcome]).inc();
+    COMMAND_DURATION
+        .with_label_values(&[command])
+        .observe(duration.as_secs_f64());
+}
+
+pub fn record_set_retry(setting: &str) {
+    SET_RETRIES.with_label_values(&[setting]).inc();
+}
+
+/// Variant name of the command without its arguments.
+pub fn command_label(cmd: &EpsonInput) -> String {
+    let name = format!("{cmd:?}");
+    match name.split_once('(') {
+        Some((name, _)) => name.to_string(),
+        None => name,
+    }
+}
+
+/// Middleware recording every request against the route it matched.
+pub async fn track_http(req: Request, next: Next) -> Response {
+    let route = match req.extensions().get::<MatchedPath>() {
+        Some(path) => path.as_str().to_string(),
+        None => "unknown".to_string(),
+    };
+    let method = req.method().to_string();
+
+    let start = Instant::now();
+    let resp = next.run(req).await;
+
+    HTTP_REQUESTS
+        .with_label_values(&[&method, &route, resp.status().as_str()])
+        .inc();
+    HTTP_REQUEST_DURATION
+        .with_label_values(&[&method, &route])
+        .observe(start.elapsed().as_secs_f64());
+    resp
+}
+
+/// Updates the projector gauges, called whenever the status is refreshed so
+/// they follow the poller rather than the scrapes.
+pub fn record_status(status: &StatusSnapshot) {
+    POWER_STATUS.reset();
+    POWER_STATUS
+        .with_label_values(&[&serde_name(&status.power_status)])
+        .set(1);
+    SOURCE.reset();
+    for source in Source::ALL {
+        let current = i64::from(status.source == Some(source));
+        SOURCE
+            .with_label_values(&[&serde_name(&source)])
+            .set(current);
+    }
+    if let Some(lamp_hours) = status.lamp_hours {
+        LAMP_HOURS.set(i64::from(lamp_hours));
+    }
+}
+
+pub async fn get_metrics() -> impl IntoResponse {
+    let mut buf = vec![];
+    if let Err(e) = TextEncoder::new().encode(&prometheus::gather(), &mut buf) {
+        error!("failed to encode metrics; error = {e}");
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    ([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], buf).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::epson_codec::PictureSetting;
+
+    use super::*;
+
+    #[test]
+    pub fn test_command_label() {
+        assert_eq!("QueryPower", command_label(&EpsonInput::QueryPower));
+        assert_eq!(
+            "SetPictureSetting",
+            command_label(&EpsonInput::SetPictureSetting(PictureSetting::Tint, 3))
+        );
+    }
+}
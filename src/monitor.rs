@@ -0,0 +1,106 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use log::{info, warn};
+use tokio::time::{sleep, Instant};
+
+use crate::{config::Config, epson_codec::PowerStatus, state::EpsonState};
+
+/// Used while warming up or cooling down so clients see the transition end
+/// promptly.
+const TRANSITION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Mute, aspect, lamp hours and errors change rarely, so they are read less
+/// often to keep the serial port free.
+const DETAILS_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Polls the projector status into the cache in `EpsonState`. The projector
+/// never reports changes on its own, so this is also what feeds
+/// `EpsonSerialPort::subscribe` when the projector is controlled with the
+/// remote.
+pub async fn monitor_start(config: &Config, state: Arc<EpsonState>) -> Result<()> {
+    poll(state, config.poll_interval).await
+}
+
+async fn poll(state: Arc<EpsonState>, poll_interval: Duration) -> Result<()> {
+    info!("polling projector status every {poll_interval:?}");
+    let mut details_at = Instant::now();
+    let mut last_power_status = None;
+    loop {
+        let details = Instant::now() >= details_at;
+        let transitioning = match state.refresh_status(details).await {
+            Ok(status) => {
+                if details {
+                    details_at = Instant::now() + DETAILS_POLL_INTERVAL;
+                }
+                // the details differ once the projector is on or off
+                if last_power_status
+                    .as_ref()
+                    .is_some_and(|last| *last != status.power_status)
+                {
+                    details_at = Instant::now();
+                }
+                last_power_status = Some(status.power_status.clone());
+                matches!(
+                    status.power_status,
+                    PowerStatus::Warmup | PowerStatus::CoolDown
+                )
+            }
+            Err(e) => {
+                warn!("failed to refresh projector status; error = {e}");
+                false
+            }
+        };
+        state.record_poll();
+        sleep(if transitioning {
+            poll_interval.min(TRANSITION_POLL_INTERVAL)
+        } else {
+            poll_interval
+        })
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tokio::time::timeout;
+
+    use crate::epson_serial_port::testing::{create_epson, lamp_on, spawn_projector};
+
+    use super::*;
+
+    #[tokio::test]
+    pub async fn test_power_change_reaches_subscribers() {
+        let (projector, epson) = create_epson();
+        let power = Arc::new(Mutex::new("PWR=00\r:"));
+        let projector_power = power.clone();
+        spawn_projector(projector, move |command| match command {
+            "PWR?" => projector_power.lock().unwrap().to_string(),
+            _ => lamp_on(command),
+        });
+        let state = Arc::new(EpsonState::new(epson));
+        let mut observed = state.epson.subscribe();
+        tokio::spawn(poll(state, Duration::from_millis(10)));
+
+        let off = Some(PowerStatus::StandbyModeNetworkOff);
+        timeout(
+            Duration::from_secs(5),
+            observed.wait_for(|o| o.power_status == off),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        // turned on with the remote
+        *power.lock().unwrap() = "PWR=01\r:";
+        let on = Some(PowerStatus::LampOn);
+        timeout(
+            Duration::from_secs(5),
+            observed.wait_for(|o| o.power_status == on),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    }
+}
@@ -0,0 +1,414 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use log::{debug, info, warn};
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS, SubscribeFilter};
+use serde_json::{json, Value};
+use tokio::time::sleep;
+
+use crate::{
+    config::Config,
+    epson_codec::{Power, Source},
+    epson_serial_port::ObservedState,
+    state::EpsonState,
+    util::{from_serde_name, serde_name},
+};
+
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+struct Topics {
+    base: String,
+    discovery_prefix: String,
+    /// `base` reduced to characters Home Assistant accepts in ids.
+    node_id: String,
+}
+
+impl Topics {
+    fn new(base: &str, discovery_prefix: &str) -> Self {
+        let node_id = base
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        Topics {
+            base: base.to_string(),
+            discovery_prefix: discovery_prefix.to_string(),
+            node_id,
+        }
+    }
+
+    fn availability(&self) -> String {
+        format!("{}/availability", self.base)
+    }
+
+    fn state(&self, entity: &str) -> String {
+        format!("{}/{entity}/state", self.base)
+    }
+
+    fn command(&self, entity: &str) -> String {
+        format!("{}/{entity}/set", self.base)
+    }
+
+    fn discovery(&self, component: &str, entity: &str) -> String {
+        format!(
+            "{}/{component}/{}/{entity}/config",
+            self.discovery_prefix, self.node_id
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum MqttCommand {
+    Power(Power),
+    Source(Source),
+}
+
+/// Publishes the projector state to MQTT, accepts power and source commands
+/// and announces itself to Home Assistant through MQTT discovery.
+pub async fn mqtt_start_client(config: &Config, state: Arc<EpsonState>) -> Result<()> {
+    let Some(host) = &config.mqtt_host else {
+        return Ok(());
+    };
+
+    let topics = Arc::new(Topics::new(
+        &config.mqtt_topic,
+        &config.mqtt_discovery_prefix,
+    ));
+    let options = mqtt_options(
+        &topics,
+        host,
+        config.mqtt_port,
+        config.mqtt_username.as_deref(),
+        config.mqtt_password.as_deref(),
+    );
+    let (client, mut event_loop) = AsyncClient::new(options, 10);
+
+    tokio::spawn(publish_states(
+        client.clone(),
+        topics.clone(),
+        state.clone(),
+    ));
+
+    info!("connecting MQTT to {host}:{}", config.mqtt_port);
+    loop {
+        match event_loop.poll().await {
+            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                info!("MQTT connected");
+                let client = client.clone();
+                let topics = topics.clone();
+                let state = state.clone();
+                tokio::spawn(async move {
+                    let serial_number = state.epson.serial_number().await;
+                    let observed = state.epson.subscribe().borrow().clone();
+                    if let Err(e) =
+                        on_connect(&client, &topics, serial_number.as_deref(), &observed).await
+                    {
+                        warn!("failed to announce MQTT; error = {e}");
+                    }
+                });
+            }
+            Ok(Event::Incoming(Packet::Publish(publish))) => {
+                let Some(cmd) = parse_command(&topics, &publish.topic, &publish.payload) else {
+                    debug!("ignoring MQTT message on {}", publish.topic);
+                    continue;
+                };
+                let state = state.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_command(&state, cmd).await {
+                        warn!("MQTT command failed; error = {e}");
+                    }
+                });
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!("MQTT connection failed; error = {e}");
+                sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+fn mqtt_options(
+    topics: &Topics,
+    host: &str,
+    port: u16,
+    username: Option<&str>,
+    password: Option<&str>,
+) -> MqttOptions {
+    let mut options = MqttOptions::new(&topics.node_id, host, port);
+    options.set_keep_alive(KEEP_ALIVE);
+    options.set_last_will(LastWill::new(
+        topics.availability(),
+        "offline",
+        QoS::AtLeastOnce,
+        true,
+    ));
+    if let Some(username) = username {
+        options.set_credentials(username, password.unwrap_or_default());
+    }
+    options
+}
+
+/// Runs after every (re)connect since the broker may have lost retained
+/// messages and always drops subscriptions of a clean session.
+async fn on_connect(
+    client: &AsyncClient,
+    topics: &Topics,
+    serial_number: Option<&str>,
+    observed: &ObservedState,
+) -> Result<()> {
+    client
+        .subscribe_many([
+            SubscribeFilter::new(topics.command("power"), QoS::AtLeastOnce),
+            SubscribeFilter::new(topics.command("source"), QoS::AtLeastOnce),
+        ])
+        .await?;
+    for (topic, payload) in discovery_messages(topics, serial_number) {
+        client
+            .publish(topic, QoS::AtLeastOnce, true, payload.to_string())
+            .await?;
+    }
+    for (topic, payload) in state_messages(topics, observed) {
+        client
+            .publish(topic, QoS::AtLeastOnce, true, payload)
+            .await?;
+    }
+    client
+        .publish(topics.availability(), QoS::AtLeastOnce, true, "online")
+        .await?;
+    Ok(())
+}
+
+/// Publishes every change seen on the serial port, which the monitor keeps
+/// up to date.
+async fn publish_states(client: AsyncClient, topics: Arc<Topics>, state: Arc<EpsonState>) {
+    let mut observed = state.epson.subscribe();
+    while observed.changed().await.is_ok() {
+        let current = observed.borrow_and_update().clone();
+        for (topic, payload) in state_messages(&topics, &current) {
+            if let Err(e) = client.publish(topic, QoS::AtLeastOnce, true, payload).await {
+                warn!("failed to publish MQTT state; error = {e}");
+            }
+        }
+    }
+}
+
+async fn handle_command(state: &EpsonState, cmd: MqttCommand) -> Result<()> {
+    info!("MQTT command {cmd:?}");
+    match cmd {
+        MqttCommand::Power(power) => state.epson.set_power(power).await,
+        MqttCommand::Source(source) => state.epson.set_source(source).await,
+    }
+}
+
+fn parse_command(topics: &Topics, topic: &str, payload: &[u8]) -> Option<MqttCommand> {
+    let payload = String::from_utf8_lossy(payload);
+    let payload = payload.trim();
+    if topic == topics.command("power") {
+        match payload.to_ascii_uppercase().as_str() {
+            "ON" => Some(MqttCommand::Power(Power::On)),
+            "OFF" => Some(MqttCommand::Power(Power::Off)),
+            _ => None,
+        }
+    } else if topic == topics.command("source") {
+        from_serde_name(payload).map(MqttCommand::Source)
+    } else {
+        None
+    }
+}
+
+fn state_messages(topics: &Topics, observed: &ObservedState) -> Vec<(String, String)> {
+    let mut messages = vec![];
+    if let Some(power_status) = &observed.power_status {
+        let power = match Power::from(power_status.clone()) {
+            Power::On => "ON",
+            Power::Off => "OFF",
+        };
+        messages.push((topics.state("power"), power.to_string()));
+        messages.push((topics.state("power_status"), serde_name(power_status)));
+    }
+    if let Some(source) = &observed.source {
+        messages.push((topics.state("source"), serde_name(source)));
+    }
+    messages
+}
+
+fn discovery_messages(topics: &Topics, serial_number: Option<&str>) -> Vec<(String, Value)> {
+    let mut device = json!({
+        "identifiers": [topics.node_id],
+        "name": "Epson Projector",
+        "manufacturer": "Epson",
+        "sw_version": env!("CARGO_PKG_VERSION"),
+    });
+    if let Some(serial_number) = serial_number {
+        device["serial_number"] = json!(serial_number);
+    }
+
+    vec![
+        (
+            topics.discovery("switch", "power"),
+            json!({
+                "name": "Power",
+                "unique_id": format!("{}_power", topics.node_id),
+                "state_topic": topics.state("power"),
+                "command_topic": topics.command("power"),
+                "availability_topic": topics.availability(),
+                "icon": "mdi:projector",
+                "device": device,
+            }),
+        ),
+        (
+            topics.discovery("sensor", "power_status"),
+            json!({
+                "name": "Power status",
+                "unique_id": format!("{}_power_status", topics.node_id),
+                "state_topic": topics.state("power_status"),
+                "availability_topic": topics.availability(),
+                "device": device,
+            }),
+        ),
+        (
+            topics.discovery("select", "source"),
+            json!({
+                "name": "Source",
+                "unique_id": format!("{}_source", topics.node_id),
+                "state_topic": topics.state("source"),
+                "command_topic": topics.command("source"),
+                "availability_topic": topics.availability(),
+                "options": Source::ALL.iter().map(serde_name).collect::<Vec<_>>(),
+                "device": device,
+            }),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use rumqttc::{ConnAck, ConnectReturnCode, PubAck, SubAck, SubscribeReasonCode};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::epson_codec::PowerStatus;
+
+    use super::*;
+
+    #[test]
+    pub fn test_parse_command() {
+        let topics = Topics::new("living-room/projector", "homeassistant");
+        assert_eq!("living_room_projector", topics.node_id);
+        assert_eq!(
+            Some(MqttCommand::Power(Power::On)),
+            parse_command(&topics, "living-room/projector/power/set", b"ON")
+        );
+        assert_eq!(
+            Some(MqttCommand::Source(Source::Input3Hdmi)),
+            parse_command(&topics, "living-room/projector/source/set", b"input3Hdmi")
+        );
+        assert_eq!(
+            None,
+            parse_command(&topics, "living-room/projector/source/set", b"tv")
+        );
+        assert_eq!(
+            None,
+            parse_command(&topics, "living-room/projector/power/state", b"ON")
+        );
+    }
+
+    /// Plays the broker side of the connection and returns the messages
+    /// published once the client says it is online.
+    async fn run_broker(listener: TcpListener) -> Vec<rumqttc::Publish> {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = BytesMut::new();
+        let mut published = vec![];
+        loop {
+            let packet = match rumqttc::mqttbytes::v4::read(&mut buf, 1024 * 1024) {
+                Ok(packet) => packet,
+                Err(_) => {
+                    stream.read_buf(&mut buf).await.unwrap();
+                    continue;
+                }
+            };
+
+            let mut resp = BytesMut::new();
+            match packet {
+                Packet::Connect(connect) => {
+                    let last_will = connect.last_will.unwrap();
+                    assert_eq!("epson/availability", last_will.topic);
+                    assert_eq!(&b"offline"[..], &last_will.message[..]);
+                    ConnAck::new(ConnectReturnCode::Success, false)
+                        .write(&mut resp)
+                        .unwrap();
+                }
+                Packet::Subscribe(subscribe) => {
+                    let topics: Vec<_> = subscribe.filters.iter().map(|f| &f.path).collect();
+                    assert_eq!(vec!["epson/power/set", "epson/source/set"], topics);
+                    let return_codes = subscribe
+                        .filters
+                        .iter()
+                        .map(|_| SubscribeReasonCode::Success(QoS::AtLeastOnce))
+                        .collect();
+                    SubAck::new(subscribe.pkid, return_codes)
+                        .write(&mut resp)
+                        .unwrap();
+                }
+                Packet::Publish(publish) => {
+                    PubAck::new(publish.pkid).write(&mut resp).unwrap();
+                    stream.write_all(&resp).await.unwrap();
+                    let online = publish.topic == "epson/availability";
+                    published.push(publish);
+                    if online {
+                        return published;
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            stream.write_all(&resp).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    pub async fn test_announce_to_broker() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = tokio::spawn(run_broker(listener));
+
+        let topics = Topics::new("epson", "homeassistant");
+        let options = mqtt_options(&topics, "127.0.0.1", port, None, None);
+        let (client, mut event_loop) = AsyncClient::new(options, 10);
+        tokio::spawn(async move { while event_loop.poll().await.is_ok() {} });
+
+        let observed = ObservedState {
+            power_status: Some(PowerStatus::LampOn),
+            source: Some(Source::Input3Hdmi),
+            error: None,
+        };
+        on_connect(&client, &topics, Some("X4KE1234"), &observed)
+            .await
+            .unwrap();
+
+        let published = broker.await.unwrap();
+        let messages: Vec<_> = published
+            .iter()
+            .map(|p| {
+                assert!(p.retain);
+                (p.topic.as_str(), String::from_utf8_lossy(&p.payload))
+            })
+            .collect();
+        assert_eq!(7, messages.len());
+
+        assert_eq!("homeassistant/select/epson/source/config", messages[2].0);
+        let discovery: Value = serde_json::from_str(&messages[2].1).unwrap();
+        assert_eq!("epson/source/set", discovery["command_topic"]);
+        assert_eq!("input1", discovery["options"][0]);
+        assert_eq!("X4KE1234", discovery["device"]["serial_number"]);
+
+        assert_eq!(("epson/power/state", "ON".into()), messages[3]);
+        assert_eq!(("epson/power_status/state", "lampOn".into()), messages[4]);
+        assert_eq!(("epson/source/state", "input3Hdmi".into()), messages[5]);
+        assert_eq!(("epson/availability", "online".into()), messages[6]);
+    }
+}
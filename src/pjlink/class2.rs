@@ -0,0 +1,211 @@
+use std::{
+    fs,
+    net::{IpAddr, Ipv4Addr},
+};
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+
+use super::{
+    command::{error_status_to_pjlink, format_response, power_status_to_pjlink, source_to_input},
+    PjlinkServer,
+};
+use crate::epson_serial_port::ObservedState;
+
+impl PjlinkServer {
+    /// Announces the bridge to controllers on the network with `LKUP`.
+    pub(super) async fn send_lookup(&self) -> Result<()> {
+        let msg = lookup_packet(&self.mac_address);
+        self.socket
+            .send_to(msg.as_bytes(), (Ipv4Addr::BROADCAST, self.port))
+            .await
+            .context("sending LKUP")?;
+        Ok(())
+    }
+
+    /// Answers `SRCH` broadcasts with `ACKN`.
+    pub(super) async fn run_search_responder(&self) -> Result<()> {
+        let mut buf = [0u8; 1024];
+        loop {
+            let (len, addr) = self.socket.recv_from(&mut buf).await?;
+            let msg = String::from_utf8_lossy(&buf[..len]);
+            if let Some(resp) = search_reply(&msg, &self.mac_address) {
+                debug!("PJLink search from {addr}");
+                self.socket
+                    .send_to(resp.as_bytes(), (addr.ip(), self.port))
+                    .await?;
+            }
+        }
+    }
+
+    /// Sends `POWR`, `INPT` and `ERST` notifications to the controller that
+    /// last talked Class 2 to us whenever the serial port sees them change.
+    pub(super) async fn run_notifier(&self) -> Result<()> {
+        let mut observed = self.state.epson.subscribe();
+        let mut previous = observed.borrow().clone();
+        while observed.changed().await.is_ok() {
+            let current = observed.borrow_and_update().clone();
+            let target = *self.notify_target.lock().unwrap();
+            for (target, notification) in notifications(target, &previous, &current) {
+                debug!("PJLink notify {target} {}", notification.trim());
+                if let Err(e) = self
+                    .socket
+                    .send_to(notification.as_bytes(), (target, self.port))
+                    .await
+                {
+                    warn!("failed to send PJLink notification to {target}; error = {e}");
+                }
+            }
+            previous = current;
+        }
+        Ok(())
+    }
+}
+
+fn lookup_packet(mac_address: &str) -> String {
+    format_response('2', "LKUP", mac_address)
+}
+
+/// `ACKN` if `msg` is a `SRCH` broadcast.
+fn search_reply(msg: &str, mac_address: &str) -> Option<String> {
+    if msg.trim().eq_ignore_ascii_case("%2SRCH") {
+        Some(format_response('2', "ACKN", mac_address))
+    } else {
+        None
+    }
+}
+
+/// Notifications for the fields that changed between `previous` and
+/// `current`, addressed to `target`. Nothing is sent until a controller has
+/// talked Class 2 to us.
+fn notifications(
+    target: Option<IpAddr>,
+    previous: &ObservedState,
+    current: &ObservedState,
+) -> Vec<(IpAddr, String)> {
+    let Some(target) = target else {
+        return vec![];
+    };
+
+    let mut notifications = vec![];
+    if current.power_status != previous.power_status {
+        if let Some(power_status) = &current.power_status {
+            let value = power_status_to_pjlink(power_status);
+            notifications.push(format_response('2', "POWR", value));
+        }
+    }
+    if current.source != previous.source {
+        if let Some(source) = current.source {
+            notifications.push(format_response('2', "INPT", source_to_input(source)));
+        }
+    }
+    if current.error != previous.error {
+        if let Some(error) = current.error {
+            let value = error_status_to_pjlink(error);
+            notifications.push(format_response('2', "ERST", value));
+        }
+    }
+    notifications
+        .into_iter()
+        .map(|notification| (target, notification))
+        .collect()
+}
+
+/// MAC address of the first network interface with one, used to identify the
+/// bridge in `ACKN` and `LKUP`.
+pub fn find_mac_address() -> String {
+    let mut interfaces = fs::read_dir("/sys/class/net")
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| !path.ends_with("lo"))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    interfaces.sort();
+
+    for interface in interfaces {
+        if let Ok(mac_address) = fs::read_to_string(interface.join("address")) {
+            let mac_address = mac_address.trim().to_uppercase();
+            if !mac_address.is_empty() && mac_address != "00:00:00:00:00:00" {
+                info!("PJLink using MAC address {mac_address} of {interface:?}");
+                return mac_address;
+            }
+        }
+    }
+
+    warn!("could not find a MAC address for PJLink, set PJLINK_MAC_ADDRESS");
+    "00:00:00:00:00:00".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::epson_codec::{PowerStatus, ProjectorError, Source};
+
+    use super::*;
+
+    #[test]
+    pub fn test_lookup_packet() {
+        assert_eq!(
+            "%2LKUP=00:11:22:33:44:55\r",
+            lookup_packet("00:11:22:33:44:55")
+        );
+    }
+
+    #[test]
+    pub fn test_search_reply() {
+        assert_eq!(
+            Some("%2ACKN=00:11:22:33:44:55\r".to_string()),
+            search_reply("%2SRCH\r", "00:11:22:33:44:55")
+        );
+        assert_eq!(
+            Some("%2ACKN=00:11:22:33:44:55\r".to_string()),
+            search_reply("%2srch", "00:11:22:33:44:55")
+        );
+        assert_eq!(
+            None,
+            search_reply("%2LKUP=00:11:22:33:44:55\r", "00:11:22:33:44:55")
+        );
+    }
+
+    #[test]
+    pub fn test_notifications() {
+        let target: IpAddr = Ipv4Addr::new(192, 168, 1, 10).into();
+        let off = ObservedState {
+            power_status: Some(PowerStatus::StandbyModeNetworkOff),
+            source: None,
+            error: Some(ProjectorError::NoError),
+        };
+        let on = ObservedState {
+            power_status: Some(PowerStatus::LampOn),
+            source: Some(Source::Input3Hdmi),
+            error: Some(ProjectorError::NoError),
+        };
+
+        assert_eq!(
+            vec![
+                (target, "%2POWR=1\r".to_string()),
+                (target, "%2INPT=31\r".to_string())
+            ],
+            notifications(Some(target), &off, &on)
+        );
+        // only what changed
+        let fan_error = ObservedState {
+            error: Some(ProjectorError::FanError),
+            ..on.clone()
+        };
+        assert_eq!(
+            vec![(target, "%2ERST=200000\r".to_string())],
+            notifications(Some(target), &on, &fan_error)
+        );
+        assert!(notifications(Some(target), &on, &on).is_empty());
+        // a field that is no longer known is not reported
+        assert_eq!(
+            vec![(target, "%2POWR=0\r".to_string())],
+            notifications(Some(target), &on, &off)
+        );
+        // no Class 2 controller yet
+        assert!(notifications(None, &off, &on).is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::epson_codec::{Power, PowerStatus, ProjectorError, Source};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Command {
+    pub class: char,
+    pub name: String,
+    pub param: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PjlinkError {
+    UndefinedCommand,
+    OutOfParameter,
+    UnavailableTime,
+    ProjectorFailure,
+}
+
+impl PjlinkError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            PjlinkError::UndefinedCommand => "ERR1",
+            PjlinkError::OutOfParameter => "ERR2",
+            PjlinkError::UnavailableTime => "ERR3",
+            PjlinkError::ProjectorFailure => "ERR4",
+        }
+    }
+}
+
+/// Parses `%1POWR 1` style lines; the authentication digest must already be
+/// stripped.
+pub fn parse_command(line: &str) -> Option<Command> {
+    let line = line.strip_prefix('%')?;
+    let mut chars = line.chars();
+    let class = chars.next()?;
+    let rest = chars.as_str();
+    let name = rest.get(..4)?;
+    let param = rest.get(4..)?.strip_prefix(' ')?;
+    Some(Command {
+        class,
+        name: name.to_ascii_uppercase(),
+        param: param.to_string(),
+    })
+}
+
+pub fn format_response(class: char, name: &str, value: &str) -> String {
+    format!("%{class}{name}={value}\r")
+}
+
+/// `md5(random + password)` as sent in front of the first command when
+/// authentication is enabled.
+pub fn auth_digest(random: &str, password: &str) -> String {
+    format!("{:x}", md5::compute(format!("{random}{password}")))
+}
+
+pub fn power_status_to_pjlink(power_status: &PowerStatus) -> &'static str {
+    match power_status {
+        PowerStatus::LampOn => "1",
+        PowerStatus::CoolDown => "2",
+        PowerStatus::Warmup => "3",
+        PowerStatus::StandbyModeNetworkOff
+        | PowerStatus::AbnormalityStandby
+        | PowerStatus::WirelessHdStandby => "0",
+    }
+}
+
+pub fn parse_power(param: &str) -> Option<Power> {
+    match param {
+        "1" => Some(Power::On),
+        "0" => Some(Power::Off),
+        _ => None,
+    }
+}
+
+/// PJLink inputs are a type digit (1 RGB, 2 video, 3 digital) followed by an
+/// index within that type.
+pub fn source_to_input(source: Source) -> &'static str {
+    match source {
+        Source::Input1 => "11",
+        Source::Input2DSub15 => "12",
+        Source::Input2Rgb => "13",
+        Source::Video => "21",
+        Source::VideoRca => "22",
+        Source::Input3Hdmi => "31",
+        Source::Input3DigitalRgb => "32",
+        Source::Hdmi2 => "33",
+    }
+}
+
+pub fn input_to_source(input: &str) -> Option<Source> {
+    Source::ALL
+        .into_iter()
+        .find(|source| source_to_input(*source) == input)
+}
+
+/// `ERST` reports fan, lamp, temperature, cover open, filter and other, each
+/// as 0 (ok), 1 (warning) or 2 (error).
+pub fn error_status_to_pjlink(error: ProjectorError) -> &'static str {
+    match error {
+        ProjectorError::NoError => "000000",
+        ProjectorError::FanError
+        | ProjectorError::PeltierCoolingError
+        | ProjectorError::PumpCoolingError => "200000",
+        ProjectorError::LampFailureAtPowerOn | ProjectorError::LampError => "020000",
+        ProjectorError::HighInternalTemperature => "002000",
+        ProjectorError::LampCoverOpen => "000200",
+        ProjectorError::LowAirFlow
+        | ProjectorError::AirFlowSensorError
+        | ProjectorError::CinemaFilterError => "000020",
+        ProjectorError::CapacitorDisconnected
+        | ProjectorError::AutoIrisError
+        | ProjectorError::SubsystemError
+        | ProjectorError::PowerSupplyError
+        | ProjectorError::ShutterError
+        | ProjectorError::StaticIrisError
+        | ProjectorError::BallastMismatch
+        | ProjectorError::ExhaustShutterError
+        | ProjectorError::ObstacleDetected
+        | ProjectorError::InterfaceBoardError => "000002",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse_command() {
+        assert_eq!(
+            Some(Command {
+                class: '1',
+                name: "POWR".to_string(),
+                param: "?".to_string(),
+            }),
+            parse_command("%1POWR ?")
+        );
+        assert_eq!(
+            Some(Command {
+                class: '1',
+                name: "INPT".to_string(),
+                param: "31".to_string(),
+            }),
+            parse_command("%1inpt 31")
+        );
+        assert_eq!(None, parse_command("%1POWR"));
+        assert_eq!(None, parse_command("POWR ?"));
+    }
+
+    #[test]
+    pub fn test_auth_digest() {
+        // example from the PJLink specification
+        assert_eq!(
+            "5d8409bc1c3fa39749434aa3a5c38682",
+            auth_digest("498e4a67", "JBMIAProjectorLink")
+        );
+    }
+
+    #[test]
+    pub fn test_inputs() {
+        for source in Source::ALL {
+            assert_eq!(Some(source), input_to_source(source_to_input(source)));
+        }
+        assert_eq!(None, input_to_source("99"));
+    }
+}
@@ -0,0 +1,302 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use log::{debug, error, info, warn};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream, UdpSocket},
+    time::timeout,
+};
+
+use crate::{
+    config::Config,
+    epson_codec::{Power, Source, VolumeAdjust},
+    state::EpsonState,
+};
+
+use command::{
+    auth_digest, error_status_to_pjlink, format_response, input_to_source, parse_command,
+    parse_power, power_status_to_pjlink, source_to_input, Command, PjlinkError,
+};
+
+mod class2;
+mod command;
+
+/// Controllers are disconnected after this long without a command, as
+/// required by the PJLink specification.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+const KNOWN_COMMANDS: [&str; 16] = [
+    "POWR", "INPT", "AVMT", "ERST", "LAMP", "INST", "NAME", "INF1", "INF2", "INFO", "CLSS", "SNUM",
+    "SVER", "FILT", "FREZ", "SVOL",
+];
+const CLASS2_COMMANDS: [&str; 5] = ["SNUM", "SVER", "FILT", "FREZ", "SVOL"];
+
+struct PjlinkServer {
+    state: Arc<EpsonState>,
+    name: String,
+    password: Option<String>,
+    port: u16,
+    mac_address: String,
+    socket: UdpSocket,
+    /// Controller that receives Class 2 status notifications, the last one
+    /// to send a Class 2 command.
+    notify_target: Mutex<Option<IpAddr>>,
+}
+
+/// PJLink Class 2 server so the projector can be managed by any PJLink
+/// controller. Class 1 controllers keep working since every Class 1 command is
+/// also answered.
+pub async fn pjlink_start_server(config: &Config, state: Arc<EpsonState>) -> Result<()> {
+    let Some(port) = config.pjlink_port else {
+        return Ok(());
+    };
+
+    let socket_address: SocketAddr = format!("0.0.0.0:{port}")
+        .parse()
+        .context("parse socket addr")?;
+    let listener = TcpListener::bind(socket_address)
+        .await
+        .context(format!("binding to {socket_address}"))?;
+
+    let socket = UdpSocket::bind(socket_address)
+        .await
+        .context(format!("binding udp to {socket_address}"))?;
+    socket.set_broadcast(true)?;
+
+    let mac_address = match &config.pjlink_mac_address {
+        Some(mac_address) => mac_address.clone(),
+        None => class2::find_mac_address(),
+    };
+
+    let server = Arc::new(PjlinkServer {
+        state,
+        name: config.pjlink_name.clone(),
+        password: config.pjlink_password.clone(),
+        port,
+        mac_address,
+        socket,
+        notify_target: Mutex::new(None),
+    });
+
+    if let Err(e) = server.send_lookup().await {
+        warn!("failed to announce PJLink; error = {e}");
+    }
+    let search_server = server.clone();
+    tokio::spawn(async move {
+        if let Err(e) = search_server.run_search_responder().await {
+            error!("PJLink search responder stopped; error = {e}");
+        }
+    });
+    let notify_server = server.clone();
+    tokio::spawn(async move {
+        if let Err(e) = notify_server.run_notifier().await {
+            error!("PJLink notifier stopped; error = {e}");
+        }
+    });
+
+    info!("listening PJLink on port {port}");
+    loop {
+        let (stream, addr) = listener.accept().await.context("accepting")?;
+        let server = server.clone();
+        tokio::spawn(async move {
+            debug!("PJLink client connected {addr}");
+            if let Err(e) = server.handle_client(stream, addr).await {
+                warn!("PJLink client {addr} failed; error = {e}");
+            }
+            debug!("PJLink client disconnected {addr}");
+        });
+    }
+}
+
+impl PjlinkServer {
+    async fn handle_client(&self, stream: TcpStream, addr: SocketAddr) -> Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+
+        // the digest is only sent in front of the first command
+        let mut expected_digest = match &self.password {
+            Some(password) => {
+                let random = format!("{:08x}", rand::random::<u32>());
+                writer
+                    .write_all(format!("PJLINK 1 {random}\r").as_bytes())
+                    .await?;
+                Some(auth_digest(&random, password))
+            }
+            None => {
+                writer.write_all(b"PJLINK 0\r").await?;
+                None
+            }
+        };
+
+        loop {
+            let mut buf = Vec::new();
+            let Ok(len) = timeout(IDLE_TIMEOUT, reader.read_until(b'\r', &mut buf)).await else {
+                return Ok(());
+            };
+            if len? == 0 {
+                return Ok(());
+            }
+            let line = String::from_utf8_lossy(&buf);
+            let mut line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(expected) = expected_digest.take() {
+                match line.get(..expected.len()) {
+                    Some(digest) if digest.eq_ignore_ascii_case(&expected) => {
+                        line = &line[expected.len()..];
+                    }
+                    _ => {
+                        writer.write_all(b"PJLINK ERRA\r").await?;
+                        return Ok(());
+                    }
+                }
+            }
+
+            let Some(cmd) = parse_command(line) else {
+                debug!("ignoring invalid PJLink line {line}");
+                continue;
+            };
+            if cmd.class == '2' {
+                *self.notify_target.lock().unwrap() = Some(addr.ip());
+            }
+            let value = match self.handle_command(&cmd).await {
+                Ok(value) => value,
+                Err(e) => e.code().to_string(),
+            };
+            writer
+                .write_all(format_response(cmd.class, &cmd.name, &value).as_bytes())
+                .await?;
+        }
+    }
+
+    async fn handle_command(&self, cmd: &Command) -> Result<String, PjlinkError> {
+        let class2_only = CLASS2_COMMANDS.contains(&cmd.name.as_str());
+        if cmd.class != '2' && (cmd.class != '1' || class2_only) {
+            return Err(PjlinkError::UndefinedCommand);
+        }
+
+        let epson = &self.state.epson;
+        match (cmd.name.as_str(), cmd.param.as_str()) {
+            ("POWR", "?") => {
+                let power_status = epson.get_power_status().await.map_err(projector_failure)?;
+                Ok(power_status_to_pjlink(&power_status).to_string())
+            }
+            ("POWR", param) => {
+                let power = parse_power(param).ok_or(PjlinkError::OutOfParameter)?;
+                epson.set_power(power).await.map_err(projector_failure)?;
+                Ok("OK".to_string())
+            }
+            ("INPT", "?") => {
+                self.require_power_on().await?;
+                let source = epson.get_source().await.map_err(projector_failure)?;
+                Ok(source_to_input(source).to_string())
+            }
+            ("INPT", param) => {
+                let source = input_to_source(param).ok_or(PjlinkError::OutOfParameter)?;
+                self.require_power_on().await?;
+                epson.set_source(source).await.map_err(projector_failure)?;
+                Ok("OK".to_string())
+            }
+            ("AVMT", "?") => {
+                self.require_power_on().await?;
+                let mute = epson.get_mute().await.map_err(projector_failure)?;
+                Ok(if mute { "31" } else { "30" }.to_string())
+            }
+            ("AVMT", param) => {
+                // MUTE always blanks picture and sound together, so video,
+                // audio and combined mute all map to it
+                let mute = match param {
+                    "11" | "21" | "31" => true,
+                    "10" | "20" | "30" => false,
+                    _ => return Err(PjlinkError::OutOfParameter),
+                };
+                self.require_power_on().await?;
+                epson.set_mute(mute).await.map_err(projector_failure)?;
+                Ok("OK".to_string())
+            }
+            ("ERST", "?") => {
+                let error = epson.get_error().await.map_err(projector_failure)?;
+                Ok(error_status_to_pjlink(error).to_string())
+            }
+            ("LAMP", "?") => {
+                let hours = epson.get_lamp_hours().await.map_err(projector_failure)?;
+                let hours = hours.ok_or(PjlinkError::UndefinedCommand)?;
+                let power_status = epson.get_power_status().await.map_err(projector_failure)?;
+                let lamp_on = Power::from(power_status) == Power::On;
+                Ok(format!("{hours} {}", if lamp_on { 1 } else { 0 }))
+            }
+            ("INST", "?") => Ok(Source::ALL
+                .into_iter()
+                .map(source_to_input)
+                .collect::<Vec<_>>()
+                .join(" ")),
+            ("NAME", "?") => Ok(self.name.clone()),
+            ("INF1", "?") => Ok("EPSON".to_string()),
+            ("INF2", "?") => Ok("ESC/VP21 Projector".to_string()),
+            ("INFO", "?") => Ok(epson.serial_number().await.unwrap_or_default()),
+            ("CLSS", "?") => Ok("2".to_string()),
+            ("SNUM", "?") => Ok(epson.serial_number().await.unwrap_or_default()),
+            ("SVER", "?") => Ok(env!("CARGO_PKG_VERSION").to_string()),
+            ("FILT", "?") => {
+                let hours = epson.get_filter_hours().await.map_err(projector_failure)?;
+                let hours = hours.ok_or(PjlinkError::UndefinedCommand)?;
+                Ok(hours.to_string())
+            }
+            ("FREZ", "?") => {
+                self.require_power_on().await?;
+                let freeze = epson.get_freeze().await.map_err(projector_failure)?;
+                Ok(if freeze { "1" } else { "0" }.to_string())
+            }
+            ("FREZ", param) => {
+                let freeze = match param {
+                    "1" => true,
+                    "0" => false,
+                    _ => return Err(PjlinkError::OutOfParameter),
+                };
+                self.require_power_on().await?;
+                epson.set_freeze(freeze).await.map_err(projector_failure)?;
+                Ok("OK".to_string())
+            }
+            ("SVOL", param) => {
+                let adjust = match param {
+                    "1" => VolumeAdjust::Increase,
+                    "0" => VolumeAdjust::Decrease,
+                    _ => return Err(PjlinkError::OutOfParameter),
+                };
+                self.require_power_on().await?;
+                epson
+                    .adjust_volume(adjust)
+                    .await
+                    .map_err(projector_failure)?;
+                Ok("OK".to_string())
+            }
+            (name, _) if KNOWN_COMMANDS.contains(&name) => Err(PjlinkError::OutOfParameter),
+            _ => Err(PjlinkError::UndefinedCommand),
+        }
+    }
+
+    async fn require_power_on(&self) -> Result<(), PjlinkError> {
+        let power_status = self
+            .state
+            .epson
+            .get_power_status()
+            .await
+            .map_err(projector_failure)?;
+        if Power::from(power_status) == Power::On {
+            Ok(())
+        } else {
+            Err(PjlinkError::UnavailableTime)
+        }
+    }
+}
+
+fn projector_failure(e: anyhow::Error) -> PjlinkError {
+    warn!("PJLink command failed; error = {e}");
+    PjlinkError::ProjectorFailure
+}
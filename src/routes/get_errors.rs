@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::ErrorResponse;
+use crate::{epson_codec::ProjectorError, state::EpsonState};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetErrorsResponse {
+    error: ProjectorError,
+}
+
+#[utoipa::path(
+    operation_id = "getErrors",
+    get,
+    path = "/api/v1/errors",
+    responses(
+        (status = 200, description = "current projector error", body = GetErrorsResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn get_errors(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    match _get_errors(state).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => {
+            error!("failed to get errors; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _get_errors(state: Arc<EpsonState>) -> Result<GetErrorsResponse> {
+    let error = state.epson.get_error().await?;
+    Ok(GetErrorsResponse { error })
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Sse,
+    },
+};
+use serde::{Deserialize, Serialize};
+use tokio_stream::{wrappers::WatchStream, StreamExt};
+use utoipa::ToSchema;
+
+use crate::{
+    epson_codec::{Power, PowerStatus, ProjectorError, Source},
+    epson_serial_port::ObservedState,
+    state::EpsonState,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StateEvent {
+    power_status: Option<PowerStatus>,
+    power: Option<Power>,
+    source: Option<Source>,
+    error: Option<ProjectorError>,
+}
+
+impl From<ObservedState> for StateEvent {
+    fn from(value: ObservedState) -> Self {
+        StateEvent {
+            power: value.power_status.clone().map(Power::from),
+            power_status: value.power_status,
+            source: value.source,
+            error: value.error,
+        }
+    }
+}
+
+/// Streams a `state` event with the current state on connect and again on every
+/// change, including warm-up and cool-down.
+#[utoipa::path(
+    operation_id = "getEvents",
+    get,
+    path = "/api/v1/events",
+    responses(
+        (status = 200, description = "server-sent state events", body = StateEvent, content_type = "text/event-stream")
+    )
+)]
+pub async fn get_events(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    let events = WatchStream::new(state.epson.subscribe()).map(|observed| {
+        Event::default()
+            .event("state")
+            .json_data(StateEvent::from(observed))
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::ErrorResponse;
+use crate::state::EpsonState;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFreezeResponse {
+    freeze: bool,
+}
+
+#[utoipa::path(
+    operation_id = "getFreeze",
+    get,
+    path = "/api/v1/freeze",
+    responses(
+        (status = 200, description = "current freeze state", body = GetFreezeResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn get_freeze(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    match _get_freeze(state).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => {
+            error!("failed to get freeze; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _get_freeze(state: Arc<EpsonState>) -> Result<GetFreezeResponse> {
+    let freeze = state.epson.get_freeze().await?;
+    Ok(GetFreezeResponse { freeze })
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{epson_serial_port::ConnectionState, state::EpsonState};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetHealthResponse {
+    serial: ConnectionState,
+    serial_port: String,
+    last_error: Option<String>,
+}
+
+#[utoipa::path(
+    operation_id = "getHealth",
+    get,
+    path = "/api/v1/health",
+    responses(
+        (status = 200, description = "serial port connection state", body = GetHealthResponse)
+    )
+)]
+pub async fn get_health(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    let health = state.epson.health();
+    Json(GetHealthResponse {
+        serial: health.state,
+        serial_port: health.serial_port,
+        last_error: health.last_error,
+    })
+}
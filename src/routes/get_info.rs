@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::state::EpsonState;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetInfoResponse {
+    serial_number: Option<String>,
+    serial_port: String,
+    bridge_version: String,
+}
+
+#[utoipa::path(
+    operation_id = "getInfo",
+    get,
+    path = "/api/v1/info",
+    responses(
+        (status = 200, description = "projector and bridge identity", body = GetInfoResponse)
+    )
+)]
+pub async fn get_info(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    Json(GetInfoResponse {
+        serial_number: state.epson.serial_number().await,
+        serial_port: state.epson.serial_port(),
+        bridge_version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
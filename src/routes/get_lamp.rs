@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::ErrorResponse;
+use crate::state::EpsonState;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLampResponse {
+    /// Not every model reports lamp hours.
+    lamp_hours: Option<u32>,
+    /// Not every model reports filter hours.
+    filter_hours: Option<u32>,
+}
+
+#[utoipa::path(
+    operation_id = "getLamp",
+    get,
+    path = "/api/v1/lamp",
+    responses(
+        (status = 200, description = "lamp and filter usage", body = GetLampResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn get_lamp(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    match _get_lamp(state).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => {
+            error!("failed to get lamp; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _get_lamp(state: Arc<EpsonState>) -> Result<GetLampResponse> {
+    let lamp_hours = state.epson.get_lamp_hours().await?;
+    let filter_hours = state.epson.get_filter_hours().await?;
+    Ok(GetLampResponse {
+        lamp_hours,
+        filter_hours,
+    })
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::ErrorResponse;
+use crate::state::EpsonState;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOrientationResponse {
+    horizontal_reverse: bool,
+    vertical_reverse: bool,
+}
+
+#[utoipa::path(
+    operation_id = "getOrientation",
+    get,
+    path = "/api/v1/orientation",
+    responses(
+        (status = 200, description = "current image orientation", body = GetOrientationResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn get_orientation(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    match _get_orientation(state).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => {
+            error!("failed to get orientation; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _get_orientation(state: Arc<EpsonState>) -> Result<GetOrientationResponse> {
+    Ok(GetOrientationResponse {
+        horizontal_reverse: state.epson.get_horizontal_reverse().await?,
+        vertical_reverse: state.epson.get_vertical_reverse().await?,
+    })
+}
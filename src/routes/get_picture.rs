@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::ErrorResponse;
+use crate::{
+    epson_codec::{ColorMode, PictureSetting},
+    state::EpsonState,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPictureResponse {
+    color_mode: ColorMode,
+    brightness: u8,
+    contrast: u8,
+    sharpness: u8,
+    tint: u8,
+    color_saturation: u8,
+}
+
+#[utoipa::path(
+    operation_id = "getPicture",
+    get,
+    path = "/api/v1/picture",
+    responses(
+        (status = 200, description = "current picture settings", body = GetPictureResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn get_picture(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    match _get_picture(state).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => {
+            error!("failed to get picture; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _get_picture(state: Arc<EpsonState>) -> Result<GetPictureResponse> {
+    let epson = &state.epson;
+    Ok(GetPictureResponse {
+        color_mode: epson.get_color_mode().await?,
+        brightness: epson
+            .get_picture_setting(PictureSetting::Brightness)
+            .await?,
+        contrast: epson.get_picture_setting(PictureSetting::Contrast).await?,
+        sharpness: epson.get_picture_setting(PictureSetting::Sharpness).await?,
+        tint: epson.get_picture_setting(PictureSetting::Tint).await?,
+        color_saturation: epson
+            .get_picture_setting(PictureSetting::ColorSaturation)
+            .await?,
+    })
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use serialport::SerialPortType;
+use utoipa::ToSchema;
+
+use super::ErrorResponse;
+use crate::{
+    config::{list_serial_ports, SerialPortCandidate, SerialPortSelection},
+    state::EpsonState,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSerialPortsResponse {
+    /// Port the bridge has open, `None` when listed without a running bridge.
+    pub in_use: Option<String>,
+    pub ports: Vec<SerialPortResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SerialPortResponse {
+    pub name: String,
+    /// usb, pci, bluetooth or unknown
+    pub port_type: String,
+    pub vid: Option<u16>,
+    pub pid: Option<u16>,
+    pub serial_number: Option<String>,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+    pub selected: bool,
+    pub selection: SerialPortSelection,
+}
+
+impl From<SerialPortCandidate> for SerialPortResponse {
+    fn from(value: SerialPortCandidate) -> Self {
+        let mut resp = SerialPortResponse {
+            name: value.info.port_name,
+            port_type: "unknown".to_string(),
+            vid: None,
+            pid: None,
+            serial_number: None,
+            manufacturer: None,
+            product: None,
+            selected: value.selection.is_selected(),
+            selection: value.selection,
+        };
+        match value.info.port_type {
+            SerialPortType::UsbPort(usb) => {
+                resp.port_type = "usb".to_string();
+                resp.vid = Some(usb.vid);
+                resp.pid = Some(usb.pid);
+                resp.serial_number = usb.serial_number;
+                resp.manufacturer = usb.manufacturer;
+                resp.product = usb.product;
+            }
+            SerialPortType::PciPort => resp.port_type = "pci".to_string(),
+            SerialPortType::BluetoothPort => resp.port_type = "bluetooth".to_string(),
+            SerialPortType::Unknown => {}
+        }
+        resp
+    }
+}
+
+#[utoipa::path(
+    operation_id = "getSerialPorts",
+    get,
+    path = "/api/v1/serial/ports",
+    responses(
+        (status = 200, description = "serial ports on the bridge and which one is used", body = GetSerialPortsResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn get_serial_ports(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    match _get_serial_ports(state).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => {
+            error!("failed to list serial ports; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _get_serial_ports(state: Arc<EpsonState>) -> Result<GetSerialPortsResponse> {
+    // udev enumeration blocks
+    let selector = state.epson.serial_port_selector().clone();
+    let ports = tokio::task::spawn_blocking(move || list_serial_ports(&selector)).await??;
+    Ok(GetSerialPortsResponse {
+        in_use: Some(state.epson.serial_port()),
+        ports: ports.into_iter().map(SerialPortResponse::from).collect(),
+    })
+}
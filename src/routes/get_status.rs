@@ -1,36 +1,73 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use axum::{extract::State, response::IntoResponse, Json};
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Utc};
 use log::error;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 use super::ErrorResponse;
 use crate::{
-    epson_codec::{Power, PowerStatus, Source},
-    state::EpsonState,
+    epson_codec::{Aspect, Power, PowerStatus, ProjectorError, Source},
+    state::{EpsonState, StatusSnapshot},
 };
 
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetStatusQuery {
+    /// Read the status from the projector instead of serving the cached copy.
+    fresh: Option<bool>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GetStatusResponse {
     power_status: PowerStatus,
     power: Power,
     source: Option<Source>,
+    mute: Option<bool>,
+    aspect: Option<Aspect>,
+    /// Not every model reports lamp hours.
+    lamp_hours: Option<u32>,
+    error: Option<ProjectorError>,
+    last_updated: DateTime<Utc>,
+}
+
+impl From<StatusSnapshot> for GetStatusResponse {
+    fn from(value: StatusSnapshot) -> Self {
+        GetStatusResponse {
+            power: value.power_status.clone().into(),
+            power_status: value.power_status,
+            source: value.source,
+            mute: value.mute,
+            aspect: value.aspect,
+            lamp_hours: value.lamp_hours,
+            error: value.error,
+            last_updated: value.last_updated,
+        }
+    }
 }
 
 #[utoipa::path(
     operation_id = "getStatus",
     get,
     path = "/api/v1/status",
+    params(GetStatusQuery),
     responses(
         (status = 200, description = "current status", body = GetStatusResponse),
         (status = 500, description = "error", body = ErrorResponse)
     )
 )]
-pub async fn get_status(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
-    match _get_status(state).await {
+pub async fn get_status(
+    State(state): State<Arc<EpsonState>>,
+    Query(query): Query<GetStatusQuery>,
+) -> impl IntoResponse {
+    match _get_status(state, query).await {
         Ok(resp) => Json(resp).into_response(),
         Err(e) => {
             error!("failed to get status; error = {e}");
@@ -42,18 +79,57 @@ pub async fn get_status(State(state): State<Arc<EpsonState>>) -> impl IntoRespon
     }
 }
 
-async fn _get_status(state: Arc<EpsonState>) -> Result<GetStatusResponse> {
-    let power_status = state.epson.get_power_status().await?;
-    let power: Power = power_status.clone().into();
-    let source = if power == Power::On {
-        Some(state.epson.get_source().await?)
-    } else {
+async fn _get_status(state: Arc<EpsonState>, query: GetStatusQuery) -> Result<GetStatusResponse> {
+    let cached = if query.fresh.unwrap_or(false) {
         None
+    } else {
+        state.cached_status()
     };
+    let snapshot = match cached {
+        Some(snapshot) => snapshot,
+        None => state.refresh_status(true).await?,
+    };
+    Ok(snapshot.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::epson_serial_port::testing::{create_epson, lamp_on, spawn_projector};
+
+    use super::*;
+
+    #[tokio::test]
+    pub async fn test_get_status_cached() {
+        let (projector, epson) = create_epson();
+        let commands = spawn_projector(projector, lamp_on);
+        let state = Arc::new(EpsonState::new(epson));
+        let refreshed = state.refresh_status(true).await.unwrap();
+        commands.lock().unwrap().clear();
 
-    Ok(GetStatusResponse {
-        power_status,
-        power,
-        source,
-    })
+        let status = _get_status(state, GetStatusQuery { fresh: None })
+            .await
+            .unwrap();
+        assert!(commands.lock().unwrap().is_empty());
+        assert_eq!(refreshed.last_updated, status.last_updated);
+        assert_eq!(Some(1234), status.lamp_hours);
+    }
+
+    #[tokio::test]
+    pub async fn test_get_status_fresh() {
+        let (projector, epson) = create_epson();
+        let commands = spawn_projector(projector, lamp_on);
+        let state = Arc::new(EpsonState::new(epson));
+        let refreshed = state.refresh_status(true).await.unwrap();
+        commands.lock().unwrap().clear();
+
+        let status = _get_status(state.clone(), GetStatusQuery { fresh: Some(true) })
+            .await
+            .unwrap();
+        assert!(commands.lock().unwrap().contains(&"PWR?".to_string()));
+        assert!(status.last_updated > refreshed.last_updated);
+        assert_eq!(
+            Some(status.last_updated),
+            state.cached_status().map(|cached| cached.last_updated)
+        );
+    }
 }
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::ErrorResponse;
+use crate::{
+    epson_codec::{ThreeDBrightness, ThreeDFormat, ThreeDMode},
+    state::EpsonState,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetThreeDResponse {
+    mode: ThreeDMode,
+    format: ThreeDFormat,
+    brightness: ThreeDBrightness,
+}
+
+#[utoipa::path(
+    operation_id = "get3d",
+    get,
+    path = "/api/v1/3d",
+    responses(
+        (status = 200, description = "current 3D settings", body = GetThreeDResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn get_three_d(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    match _get_three_d(state).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => {
+            error!("failed to get 3D settings; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _get_three_d(state: Arc<EpsonState>) -> Result<GetThreeDResponse> {
+    Ok(GetThreeDResponse {
+        mode: state.epson.get_three_d_mode().await?,
+        format: state.epson.get_three_d_format().await?,
+        brightness: state.epson.get_three_d_brightness().await?,
+    })
+}
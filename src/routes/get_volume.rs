@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::ErrorResponse;
+use crate::state::EpsonState;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeResponse {
+    volume: u8,
+}
+
+#[utoipa::path(
+    operation_id = "getVolume",
+    get,
+    path = "/api/v1/volume",
+    responses(
+        (status = 200, description = "current volume", body = GetVolumeResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn get_volume(State(state): State<Arc<EpsonState>>) -> impl IntoResponse {
+    match _get_volume(state).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => {
+            error!("failed to get volume; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _get_volume(state: Arc<EpsonState>) -> Result<GetVolumeResponse> {
+    let volume = state.epson.get_volume().await?;
+    Ok(GetVolumeResponse { volume })
+}
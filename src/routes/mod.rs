@@ -1,9 +1,29 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+pub mod get_errors;
+pub mod get_events;
+pub mod get_freeze;
+pub mod get_health;
+pub mod get_info;
+pub mod get_lamp;
+pub mod get_orientation;
+pub mod get_picture;
+pub mod get_serial_ports;
 pub mod get_status;
+pub mod get_three_d;
+pub mod get_volume;
+pub mod patch_picture;
+pub mod post_aspect;
+pub mod post_freeze;
+pub mod post_key;
+pub mod post_mute;
+pub mod post_orientation;
 pub mod post_power;
+pub mod post_raw;
 pub mod post_source;
+pub mod post_three_d;
+pub mod post_volume;
 
 #[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
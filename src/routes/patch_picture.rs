@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{EmptyResponse, ErrorResponse};
+use crate::{
+    epson_codec::{ColorMode, PictureSetting},
+    state::EpsonState,
+};
+
+/// Only the fields present are changed. The color mode is applied first since
+/// switching modes resets the other adjustments.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchPictureRequest {
+    color_mode: Option<ColorMode>,
+    brightness: Option<u8>,
+    contrast: Option<u8>,
+    sharpness: Option<u8>,
+    tint: Option<u8>,
+    color_saturation: Option<u8>,
+}
+
+#[utoipa::path(
+    operation_id = "updatePicture",
+    patch,
+    path = "/api/v1/picture",
+    responses(
+        (status = 200, description = "picture settings updated", body = EmptyResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn patch_picture(
+    State(state): State<Arc<EpsonState>>,
+    Json(req): Json<PatchPictureRequest>,
+) -> impl IntoResponse {
+    match _patch_picture(state, req).await {
+        Ok(_) => Json(EmptyResponse::new()).into_response(),
+        Err(e) => {
+            error!("failed to update picture; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _patch_picture(state: Arc<EpsonState>, req: PatchPictureRequest) -> Result<()> {
+    if let Some(color_mode) = req.color_mode {
+        state.epson.set_color_mode(color_mode).await?;
+    }
+
+    let settings = [
+        (PictureSetting::Brightness, req.brightness),
+        (PictureSetting::Contrast, req.contrast),
+        (PictureSetting::Sharpness, req.sharpness),
+        (PictureSetting::Tint, req.tint),
+        (PictureSetting::ColorSaturation, req.color_saturation),
+    ];
+    for (setting, value) in settings {
+        if let Some(value) = value {
+            state.epson.set_picture_setting(setting, value).await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{EmptyResponse, ErrorResponse};
+use crate::{epson_codec::Aspect, state::EpsonState};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostAspectRequest {
+    aspect: Aspect,
+}
+
+#[utoipa::path(
+    operation_id = "setAspect",
+    post,
+    path = "/api/v1/aspect",
+    responses(
+        (status = 200, description = "aspect set", body = EmptyResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn post_aspect(
+    State(state): State<Arc<EpsonState>>,
+    Json(req): Json<PostAspectRequest>,
+) -> impl IntoResponse {
+    match _post_aspect(state, req.aspect).await {
+        Ok(_) => Json(EmptyResponse::new()).into_response(),
+        Err(e) => {
+            error!("failed to set aspect; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _post_aspect(state: Arc<EpsonState>, aspect: Aspect) -> Result<()> {
+    state.epson.set_aspect(aspect).await?;
+    Ok(())
+}
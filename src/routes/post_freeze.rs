@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{EmptyResponse, ErrorResponse};
+use crate::state::EpsonState;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostFreezeRequest {
+    freeze: bool,
+}
+
+#[utoipa::path(
+    operation_id = "setFreeze",
+    post,
+    path = "/api/v1/freeze",
+    responses(
+        (status = 200, description = "freeze set", body = EmptyResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn post_freeze(
+    State(state): State<Arc<EpsonState>>,
+    Json(req): Json<PostFreezeRequest>,
+) -> impl IntoResponse {
+    match _post_freeze(state, req.freeze).await {
+        Ok(_) => Json(EmptyResponse::new()).into_response(),
+        Err(e) => {
+            error!("failed to set freeze; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _post_freeze(state: Arc<EpsonState>, freeze: bool) -> Result<()> {
+    state.epson.set_freeze(freeze).await?;
+    Ok(())
+}
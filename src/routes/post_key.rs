@@ -0,0 +1,76 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Result};
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+use utoipa::ToSchema;
+
+use super::{EmptyResponse, ErrorResponse};
+use crate::{epson_codec::RemoteKey, state::EpsonState};
+
+const DEFAULT_KEY_DELAY_MS: u64 = 500;
+/// Keeps one request from holding up the poller and other clients for long.
+const MAX_KEYS: usize = 32;
+const MAX_KEY_DELAY_MS: u64 = 5000;
+
+/// Either a single `key` or a sequence of `keys`, not both. `delayMs` is the
+/// pause between keys in a sequence so the projector menu can keep up. At most
+/// 32 keys and 5000ms.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostKeyRequest {
+    key: Option<RemoteKey>,
+    keys: Option<Vec<RemoteKey>>,
+    delay_ms: Option<u64>,
+}
+
+#[utoipa::path(
+    operation_id = "pressKey",
+    post,
+    path = "/api/v1/key",
+    responses(
+        (status = 200, description = "keys pressed", body = EmptyResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn post_key(
+    State(state): State<Arc<EpsonState>>,
+    Json(req): Json<PostKeyRequest>,
+) -> impl IntoResponse {
+    match _post_key(state, req).await {
+        Ok(_) => Json(EmptyResponse::new()).into_response(),
+        Err(e) => {
+            error!("failed to press key; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _post_key(state: Arc<EpsonState>, req: PostKeyRequest) -> Result<()> {
+    let keys = match (req.key, req.keys) {
+        (Some(key), None) => vec![key],
+        (None, Some(keys)) => keys,
+        _ => return Err(anyhow!("exactly one of key or keys must be set")),
+    };
+    if keys.len() > MAX_KEYS {
+        return Err(anyhow!("at most {MAX_KEYS} keys can be sent at once"));
+    }
+    let delay_ms = req.delay_ms.unwrap_or(DEFAULT_KEY_DELAY_MS);
+    if delay_ms > MAX_KEY_DELAY_MS {
+        return Err(anyhow!("delayMs must be at most {MAX_KEY_DELAY_MS}"));
+    }
+    let delay = Duration::from_millis(delay_ms);
+
+    for (i, key) in keys.into_iter().enumerate() {
+        if i > 0 {
+            sleep(delay).await;
+        }
+        state.epson.press_key(key).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{EmptyResponse, ErrorResponse};
+use crate::{epson_codec::MuteColor, state::EpsonState};
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostMuteRequest {
+    mute: bool,
+    color: Option<MuteColor>,
+}
+
+#[utoipa::path(
+    operation_id = "setMute",
+    post,
+    path = "/api/v1/mute",
+    responses(
+        (status = 200, description = "mute set", body = EmptyResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn post_mute(
+    State(state): State<Arc<EpsonState>>,
+    Json(req): Json<PostMuteRequest>,
+) -> impl IntoResponse {
+    match _post_mute(state, req).await {
+        Ok(_) => Json(EmptyResponse::new()).into_response(),
+        Err(e) => {
+            error!("failed to set mute; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _post_mute(state: Arc<EpsonState>, req: PostMuteRequest) -> Result<()> {
+    if let Some(color) = req.color {
+        state.epson.set_mute_color(color).await?;
+    }
+    state.epson.set_mute(req.mute).await?;
+    Ok(())
+}
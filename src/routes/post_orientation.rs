@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{EmptyResponse, ErrorResponse};
+use crate::state::EpsonState;
+
+/// Only the fields present are changed. Ceiling mounts reverse both axes, rear
+/// projection only the horizontal one.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostOrientationRequest {
+    horizontal_reverse: Option<bool>,
+    vertical_reverse: Option<bool>,
+}
+
+#[utoipa::path(
+    operation_id = "setOrientation",
+    post,
+    path = "/api/v1/orientation",
+    responses(
+        (status = 200, description = "orientation set", body = EmptyResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn post_orientation(
+    State(state): State<Arc<EpsonState>>,
+    Json(req): Json<PostOrientationRequest>,
+) -> impl IntoResponse {
+    match _post_orientation(state, req).await {
+        Ok(_) => Json(EmptyResponse::new()).into_response(),
+        Err(e) => {
+            error!("failed to set orientation; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _post_orientation(state: Arc<EpsonState>, req: PostOrientationRequest) -> Result<()> {
+    if let Some(horizontal_reverse) = req.horizontal_reverse {
+        state
+            .epson
+            .set_horizontal_reverse(horizontal_reverse)
+            .await?;
+    }
+    if let Some(vertical_reverse) = req.vertical_reverse {
+        state.epson.set_vertical_reverse(vertical_reverse).await?;
+    }
+    Ok(())
+}
@@ -12,7 +12,7 @@ use crate::{epson_codec::Power, state::EpsonState};
 #[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PostPowerRequest {
-    power: Power,
+    pub power: Power,
 }
 
 #[utoipa::path(
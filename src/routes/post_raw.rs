@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::ErrorResponse;
+use crate::state::EpsonState;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostRawRequest {
+    /// ESC/VP21 command without the trailing CR, e.g. `LUMINANCE?`
+    command: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostRawResponse {
+    /// Reply line, empty if the projector only returned the prompt, or `ERR`
+    response: String,
+}
+
+/// Only registered when `RAW_COMMANDS_ENABLED=true`.
+#[utoipa::path(
+    operation_id = "sendRaw",
+    post,
+    path = "/api/v1/raw",
+    responses(
+        (status = 200, description = "projector reply", body = PostRawResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn post_raw(
+    State(state): State<Arc<EpsonState>>,
+    Json(req): Json<PostRawRequest>,
+) -> impl IntoResponse {
+    match _post_raw(state, req.command).await {
+        Ok(resp) => Json(resp).into_response(),
+        Err(e) => {
+            error!("failed to send raw command; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _post_raw(state: Arc<EpsonState>, command: String) -> Result<PostRawResponse> {
+    let response = state.epson.send_raw(&command).await?;
+    Ok(PostRawResponse { response })
+}
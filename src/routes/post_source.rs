@@ -12,7 +12,7 @@ use crate::{epson_codec::Source, state::EpsonState};
 #[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PostSourceRequest {
-    source: Source,
+    pub source: Source,
 }
 
 #[utoipa::path(
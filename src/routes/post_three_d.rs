@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{EmptyResponse, ErrorResponse};
+use crate::{
+    epson_codec::{ThreeDBrightness, ThreeDFormat, ThreeDMode},
+    state::EpsonState,
+};
+
+/// Only the fields present are changed.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostThreeDRequest {
+    mode: Option<ThreeDMode>,
+    format: Option<ThreeDFormat>,
+    brightness: Option<ThreeDBrightness>,
+}
+
+#[utoipa::path(
+    operation_id = "set3d",
+    post,
+    path = "/api/v1/3d",
+    responses(
+        (status = 200, description = "3D settings set", body = EmptyResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn post_three_d(
+    State(state): State<Arc<EpsonState>>,
+    Json(req): Json<PostThreeDRequest>,
+) -> impl IntoResponse {
+    match _post_three_d(state, req).await {
+        Ok(_) => Json(EmptyResponse::new()).into_response(),
+        Err(e) => {
+            error!("failed to set 3D settings; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _post_three_d(state: Arc<EpsonState>, req: PostThreeDRequest) -> Result<()> {
+    if let Some(format) = req.format {
+        state.epson.set_three_d_format(format).await?;
+    }
+    if let Some(mode) = req.mode {
+        state.epson.set_three_d_mode(mode).await?;
+    }
+    if let Some(brightness) = req.brightness {
+        state.epson.set_three_d_brightness(brightness).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use axum::{extract::State, response::IntoResponse, Json};
+use log::error;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{EmptyResponse, ErrorResponse};
+use crate::{epson_codec::VolumeAdjust, state::EpsonState};
+
+/// Either an absolute `volume` or a single `adjust` step, not both.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PostVolumeRequest {
+    volume: Option<u8>,
+    adjust: Option<VolumeAdjust>,
+}
+
+#[utoipa::path(
+    operation_id = "setVolume",
+    post,
+    path = "/api/v1/volume",
+    responses(
+        (status = 200, description = "volume set", body = EmptyResponse),
+        (status = 500, description = "error", body = ErrorResponse)
+    )
+)]
+pub async fn post_volume(
+    State(state): State<Arc<EpsonState>>,
+    Json(req): Json<PostVolumeRequest>,
+) -> impl IntoResponse {
+    match _post_volume(state, req).await {
+        Ok(_) => Json(EmptyResponse::new()).into_response(),
+        Err(e) => {
+            error!("failed to set volume; error = {e}");
+            Json(ErrorResponse {
+                message: format!("{e}"),
+            })
+            .into_response()
+        }
+    }
+}
+
+async fn _post_volume(state: Arc<EpsonState>, req: PostVolumeRequest) -> Result<()> {
+    match (req.volume, req.adjust) {
+        (Some(volume), None) => state.epson.set_volume(volume).await,
+        (None, Some(adjust)) => state.epson.adjust_volume(adjust).await,
+        _ => Err(anyhow!("exactly one of volume or adjust must be set")),
+    }
+}
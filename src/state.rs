@@ -1,5 +1,153 @@
-use crate::epson_serial_port::EpsonSerialPort;
+use std::{
+    sync::{Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use log::warn;
+
+use crate::{
+    epson_codec::{Aspect, Power, PowerStatus, ProjectorError, Source},
+    epson_serial_port::EpsonSerialPort,
+    metrics,
+};
 
 pub struct EpsonState {
     pub epson: EpsonSerialPort,
+    /// Last status read by the poller or a fresh `GET /api/v1/status`.
+    status: RwLock<Option<StatusSnapshot>>,
+    /// When the monitor last finished a poll, whether or not the projector
+    /// answered.
+    last_poll: Mutex<Option<Instant>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StatusSnapshot {
+    pub power_status: PowerStatus,
+    pub source: Option<Source>,
+    pub mute: Option<bool>,
+    pub aspect: Option<Aspect>,
+    pub lamp_hours: Option<u32>,
+    /// `None` if `ERR?` failed, e.g. an error code this bridge does not know.
+    pub error: Option<ProjectorError>,
+    pub last_updated: DateTime<Utc>,
+}
+
+impl EpsonState {
+    pub fn new(epson: EpsonSerialPort) -> Self {
+        EpsonState {
+            epson,
+            status: RwLock::new(None),
+            last_poll: Mutex::new(None),
+        }
+    }
+
+    pub fn record_poll(&self) {
+        *self.last_poll.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// True while the monitor keeps polling, even if the projector is off or
+    /// unplugged. Whether it answers is `EpsonSerialPort::is_ready`.
+    pub fn is_polling(&self, max_age: Duration) -> bool {
+        self.last_poll
+            .lock()
+            .unwrap()
+            .is_some_and(|last_poll| last_poll.elapsed() <= max_age)
+    }
+
+    pub fn cached_status(&self) -> Option<StatusSnapshot> {
+        self.status.read().unwrap().clone()
+    }
+
+    /// Reads the power status, and the source while on, from the projector
+    /// and caches it. With `details` the mute, aspect, lamp hours and error are
+    /// read too, otherwise they are kept from the last cached status. Only a
+    /// failed power query is an error, the other fields are `None` instead.
+    pub async fn refresh_status(&self, details: bool) -> Result<StatusSnapshot> {
+        let power_status = self.epson.get_power_status().await?;
+        let on = Power::from(power_status.clone()) == Power::On;
+        let source = if on {
+            optional("source", self.epson.get_source().await)
+        } else {
+            None
+        };
+
+        let mut snapshot = StatusSnapshot {
+            power_status,
+            source,
+            mute: None,
+            aspect: None,
+            lamp_hours: None,
+            error: None,
+            last_updated: Utc::now(),
+        };
+        if details {
+            if on {
+                snapshot.mute = optional("mute", self.epson.get_mute().await);
+                // answered with ERR while there is no signal
+                snapshot.aspect = optional("aspect", self.epson.get_aspect().await);
+            }
+            snapshot.lamp_hours =
+                optional("lamp hours", self.epson.get_lamp_hours().await).flatten();
+            snapshot.error = optional("projector error", self.epson.get_error().await);
+        } else if let Some(previous) = self.cached_status() {
+            if on {
+                snapshot.mute = previous.mute;
+                snapshot.aspect = previous.aspect;
+            }
+            snapshot.lamp_hours = previous.lamp_hours;
+            snapshot.error = previous.error;
+        }
+
+        metrics::record_status(&snapshot);
+        *self.status.write().unwrap() = Some(snapshot.clone());
+        Ok(snapshot)
+    }
+}
+
+fn optional<T>(name: &str, result: Result<T>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("failed to read {name}; error = {e}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::epson_serial_port::testing::{create_epson, lamp_on, spawn_projector};
+
+    use super::*;
+
+    #[tokio::test]
+    pub async fn test_is_polling() {
+        let (_projector, epson) = create_epson();
+        let state = EpsonState::new(epson);
+        assert!(!state.is_polling(Duration::from_secs(1)));
+        state.record_poll();
+        assert!(state.is_polling(Duration::from_secs(1)));
+    }
+
+    #[tokio::test]
+    pub async fn test_refresh_status_keeps_details() {
+        let (projector, epson) = create_epson();
+        let commands = spawn_projector(projector, lamp_on);
+        let state = EpsonState::new(epson);
+
+        let status = state.refresh_status(true).await.unwrap();
+        assert_eq!(Some(1234), status.lamp_hours);
+        assert_eq!(6, commands.lock().unwrap().len());
+
+        commands.lock().unwrap().clear();
+        let status = state.refresh_status(false).await.unwrap();
+        assert_eq!(vec!["PWR?", "SOURCE?"], *commands.lock().unwrap());
+        assert_eq!(Some(Source::Input3Hdmi), status.source);
+        assert_eq!(Some(false), status.mute);
+        assert_eq!(Some(Aspect::Normal), status.aspect);
+        assert_eq!(Some(1234), status.lamp_hours);
+        assert_eq!(Some(ProjectorError::NoError), status.error);
+    }
 }
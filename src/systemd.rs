@@ -0,0 +1,41 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use sd_notify::NotifyState;
+use tokio::{sync::oneshot, time::interval};
+
+use crate::{config::Config, state::EpsonState};
+
+/// Tells systemd the bridge is up once the HTTP port is bound and, when the
+/// unit sets `WatchdogSec`, pings the watchdog while the monitor keeps polling
+/// so a hung poller gets the bridge restarted. A projector that is off or
+/// unplugged is left to `/readyz`, restarting would not bring it back. Does
+/// nothing when not started by systemd.
+pub async fn systemd_notify_start(
+    config: &Config,
+    state: Arc<EpsonState>,
+    listening: oneshot::Receiver<()>,
+) -> Result<()> {
+    listening.await.context("waiting for the http server")?;
+    sd_notify::notify(false, &[NotifyState::Ready]).context("notifying systemd ready")?;
+
+    let mut usec = 0;
+    if !sd_notify::watchdog_enabled(false, &mut usec) {
+        return Ok(());
+    }
+    let period = Duration::from_micros(usec) / 2;
+    let poll_max_age = config.poll_max_age();
+    info!("pinging systemd watchdog every {period:?}");
+
+    let mut watchdog = interval(period);
+    loop {
+        watchdog.tick().await;
+        if !state.is_polling(poll_max_age) {
+            warn!("monitor has not polled for {poll_max_age:?}, skipping systemd watchdog");
+            continue;
+        }
+        debug!("pinging systemd watchdog");
+        sd_notify::notify(false, &[NotifyState::Watchdog]).context("pinging systemd watchdog")?;
+    }
+}